@@ -1,12 +1,20 @@
 use new_vdf_parser::appinfo_vdf_parser::open_appinfo_vdf;
+use notify_debouncer_mini::notify::{RecommendedWatcher, RecursiveMode};
+use notify_debouncer_mini::{new_debouncer, DebounceEventResult, Debouncer};
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
 use std::collections::HashMap;
 use std::fs;
+use std::io;
 use std::path::{Path, PathBuf};
-use std::sync::Mutex;
-use std::time::SystemTime;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant, SystemTime};
 use sysinfo::System;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_opener::OpenerExt;
+use tauri_plugin_store::StoreExt;
+use tauri_plugin_updater::UpdaterExt;
 use walkdir::WalkDir;
 
 // ─── Data structures ────────────────────────────────────────────────
@@ -19,12 +27,76 @@ pub struct Profile {
     pub is_backup: bool,
     pub path: String,
     pub last_login: String,
+    /// `last_login` as a Unix timestamp (seconds), 0 for never; sorting compares this since the
+    /// formatted string doesn't sort chronologically in every `TimeFormat`
+    pub last_login_secs: u64,
+    /// As a string because it doesn't fit in a JavaScript number
+    pub steamid64: Option<String>,
+    pub avatar_path: Option<String>,
+    /// False when the account has no localconfig.vdf, so `name` is just the folder id
+    pub has_local_config: bool,
+    /// Set when the profile folder could not be listed (e.g. permission denied), in which case
+    /// `game_count` is 0 because nothing could be read, not because there are no saves
+    pub read_error: Option<String>,
+    /// The `0` folder Steam uses when nobody is logged in (offline mode, Big Picture). It can
+    /// hold real saves but belongs to no account.
+    pub is_offline_account: bool,
+    /// Drive letter on Windows, mount point elsewhere; empty when it can't be determined
+    pub drive: String,
+    /// Size of all game folders. Walking them is slow, so this is 0 (and `total_size` empty)
+    /// unless `get_profiles` is asked for sizes.
+    pub total_size_bytes: u64,
+    pub total_size: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GameInfo {
     pub id: String,
     pub name: String,
+    /// From the profile's localconfig.vdf, "Never" when Steam has no record
+    pub last_played: String,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub enum ProfileSort {
+    #[default]
+    LastLogin,
+    Name,
+    GameCount,
+}
+
+/// What a swap does with the data already in a target game folder
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub enum SwapMode {
+    /// Delete the target's folder and copy the source's in its place
+    #[default]
+    Replace,
+    /// Copy only files that are missing in the target or newer in the source; files only the
+    /// target has are kept
+    MergeNewer,
+}
+
+/// How a swap backs up a target game folder before replacing it
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub enum BackupStrategy {
+    /// Move the folder into the backup when both are on the same volume, copy otherwise
+    #[default]
+    Auto,
+    /// Hard-link the folder's files into the backup on the same volume, copy otherwise. The
+    /// target stays in place until the swap replaces it, and the links keep the old data.
+    Hardlink,
+    /// Always take a full copy
+    Copy,
+}
+
+/// How `merge_profiles` resolves a file that exists in both profiles
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum MergeStrategy {
+    /// Keep whichever copy was modified last
+    NewerWins,
+    SourceWins,
+    /// Only add files the target doesn't have yet
+    SkipExisting,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -35,24 +107,477 @@ pub struct SwapSummary {
     pub source_total_size: u64,
     pub source_file_count: usize,
     pub source_folder_count: usize,
+    /// The games the swap will actually touch, after expanding `*` and applying exclusions
+    pub effective_game_ids: Vec<String>,
+    /// Steam may hold userdata files open or rewrite them, see `close_steam`
+    pub steam_running: bool,
+    /// What each game adds to the totals above, in `effective_game_ids` order
+    pub per_game: Vec<GameStats>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameStats {
+    pub game_id: String,
+    pub game_name: String,
+    pub size: u64,
+    pub file_count: usize,
+    pub folder_count: usize,
+    pub last_modified: String,
+}
+
+/// Throughput of one finished `execute_swap`, kept in the settings store
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwapStats {
+    /// Unix timestamp (seconds) of when the swap finished
+    pub timestamp: u64,
+    pub bytes_copied: u64,
+    pub files_copied: usize,
+    pub elapsed_ms: u64,
+    /// Everything the run wrote (backups included) over `elapsed_ms`
+    pub mb_per_sec: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwapFile {
+    pub game_id: String,
+    /// Relative to the game folder
+    pub path: String,
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwapFileList {
+    pub files: Vec<SwapFile>,
+    /// Files the swap would copy, including those cut off by the limit
+    pub total_files: usize,
+    pub truncated: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EstimateReport {
+    /// Bytes written into targets: source size times the number of targets
+    pub total_bytes: u64,
+    /// None when the throughput couldn't be measured
+    pub estimated_seconds: Option<u64>,
+    pub throughput_mb_per_sec: Option<f64>,
+}
+
+/// What swapping one game would change in the target; paths are relative to the game folder
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileDiff {
+    /// Only in the source, will be created in the target
+    pub added: Vec<String>,
+    /// Only in the target, will be removed
+    pub deleted: Vec<String>,
+    /// In both but with different contents
+    pub modified: Vec<String>,
+    pub unchanged_count: usize,
+    /// Size of the added and modified files plus the deleted ones
+    pub changed_bytes: u64,
+}
+
+/// How far a live game folder has moved on from its backup
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DriftReport {
+    /// Files created since the backup
+    pub added: usize,
+    pub modified: usize,
+    /// Files in the backup that no longer exist
+    pub removed: usize,
+    pub changed_bytes: u64,
+    /// When the backup was taken, "Unknown" without a manifest entry
+    pub backup_created: String,
+}
+
+/// Identity hints from an account's localconfig.vdf; every field is optional because Steam
+/// doesn't always write them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileDetails {
+    pub id: String,
+    pub steamid64: Option<String>,
+    pub persona_name: Option<String>,
+    pub friend_count: usize,
+    pub last_played_game_id: Option<String>,
+    pub last_played: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CurrentUser {
+    /// Account id, the same as the userdata folder name
+    pub id: String,
+    pub persona_name: Option<String>,
+}
+
+/// An account Steam remembers a login for, whether or not it has a userdata folder
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KnownUser {
+    /// Account id, the same as the userdata folder name
+    pub id: String,
+    pub steamid64: String,
+    pub persona_name: Option<String>,
+    pub most_recent: bool,
+    pub has_userdata: bool,
+    /// Games with save data, 0 for accounts without a userdata folder
+    pub game_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileDiff {
+    pub only_in_a: Vec<GameInfo>,
+    pub only_in_b: Vec<GameInfo>,
+    pub in_both: Vec<GameInfo>,
 }
 
+/// Optional knobs shared by `get_swap_summary` and `execute_swap`; every field may be omitted
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SwapOptions {
+    /// Games to leave out, typically combined with the `*` all-games selection
+    pub exclude_game_ids: Vec<String>,
+    /// Clone files with copy-on-write where the filesystem supports it
+    pub reflink: bool,
+    /// Must be set for `backup_target_ids` to be honoured
+    pub allow_backup_targets: bool,
+    /// Backups (`<account id>/<snapshot>`) to swap into, in addition to `target_ids`
+    pub backup_target_ids: Vec<String>,
+    /// Don't follow symlinks or junctions inside game folders; they are skipped instead
+    pub skip_reparse_points: bool,
+    /// Extra folders relative to the profile base (e.g. `config/some_game`), swapped like a
+    /// game folder
+    pub extra_paths: Vec<String>,
+    /// Caps file copy throughput (backups included). Metadata operations such as creating or
+    /// deleting folders are not throttled.
+    pub max_bytes_per_sec: Option<u64>,
+    /// Leave the source's remotecache.vdf out so Steam Cloud rebuilds it for the target. On by
+    /// default; backups always keep it. Turning it off makes targets inherit the source's cloud
+    /// sync state instead.
+    pub skip_remotecache: bool,
+    /// Copy files through a buffer of this many bytes instead of the OS copy routine, which
+    /// can be faster for saves made of thousands of tiny files
+    pub copy_buffer_size: Option<usize>,
+    /// Label put into the name of every snapshot the swap backs a target up into
+    pub backup_label: Option<String>,
+    pub mode: SwapMode,
+    /// Swap only these files (relative to each game folder) instead of whole folders. Each
+    /// target file is backed up on its own and the rest of the target folder is left alone.
+    pub files: Option<Vec<String>>,
+    /// Stop at the first error instead of carrying on with the remaining games and targets.
+    /// The game that failed is rolled back from the backup taken for it.
+    pub stop_on_error: bool,
+    /// Let targets be ids without a profile (or backup) folder, which the swap then creates
+    pub allow_new_targets: bool,
+    /// Write the finished swap's report (per-target statuses and totals) to this path, as JSON
+    /// for a `.json` file and markdown otherwise
+    pub write_report: Option<String>,
+    /// Only applies to replace mode; merging always backs up with a copy
+    pub backup_strategy: BackupStrategy,
+    /// Flush every copied file and folder to disk as the swap goes, so a crash or power loss
+    /// can't lose data a finished swap reported as copied. Much slower, especially for saves
+    /// made of many small files, since each file waits for the disk instead of the page cache.
+    pub durable: bool,
+    /// How many target games are swapped at once (backup, clear and copy stay in order within
+    /// each). Above 1 the operations split `max_bytes_per_sec` between them, and every target
+    /// copies from the source instead of from an earlier target's copy.
+    pub concurrency: usize,
+}
+
+impl Default for SwapOptions {
+    fn default() -> Self {
+        SwapOptions {
+            exclude_game_ids: Vec::new(),
+            reflink: false,
+            allow_backup_targets: false,
+            backup_target_ids: Vec::new(),
+            skip_reparse_points: false,
+            extra_paths: Vec::new(),
+            max_bytes_per_sec: None,
+            skip_remotecache: true,
+            copy_buffer_size: None,
+            backup_label: None,
+            mode: SwapMode::Replace,
+            files: None,
+            stop_on_error: false,
+            allow_new_targets: false,
+            write_report: None,
+            backup_strategy: BackupStrategy::Auto,
+            concurrency: 1,
+            durable: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SwapResult {
     pub success: bool,
     pub message: String,
     pub details: Vec<String>,
+    pub games_swapped: usize,
+    /// Targets that received at least one game
+    pub targets_affected: usize,
+    /// Bytes written into targets (backups not included)
+    pub bytes_copied: u64,
+    /// Files written into targets, reflinked ones included (backups not included)
+    pub files_copied: usize,
+    pub elapsed_ms: u64,
+    /// Bytes written into targets and backups (rollbacks included), all of which `elapsed_ms`
+    /// covers
+    #[serde(default)]
+    pub bytes_written: u64,
+    /// Per-target, per-game outcome of a swap; the same information as `details`, structured
+    pub targets: Vec<TargetResult>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TargetResult {
+    pub target_id: String,
+    pub target_name: String,
+    pub games: Vec<GameSwapStatus>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameSwapStatus {
+    pub game_id: String,
+    pub status: SwapStatus,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfirmResult {
+    pub target_id: String,
+    pub game_id: String,
+    pub status: ConfirmStatus,
+}
+
+/// How a target's game folder compares with the source's, by `fingerprint_folder`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ConfirmStatus {
+    Match,
+    Mismatch,
+    /// The target has no folder for this game
+    Missing,
+    /// The source has no data for this game, so there is nothing to compare
+    Skipped,
+    Failed(String),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum SwapStatus {
+    Swapped,
+    /// The source has no data for this game
+    Skipped,
+    /// The target's data was backed up but clearing it failed partway, so it may be missing
+    /// some files and should be restored from that backup (e.g. with `undo_swap`)
+    BackedUpOnly,
+    Failed(String),
+}
+
+impl SwapResult {
+    fn failed(message: impl Into<String>) -> Self {
+        SwapResult {
+            success: false,
+            message: message.into(),
+            ..Default::default()
+        }
+    }
+}
+
+/// One source → targets swap of an `execute_swap_batch`, with the same fields as `execute_swap`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwapPlan {
+    pub source_id: String,
+    #[serde(default)]
+    pub source_is_backup: bool,
+    pub target_ids: Vec<String>,
+    pub game_ids: Vec<String>,
+    #[serde(default)]
+    pub options: SwapOptions,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BatchResult {
+    pub success: bool,
+    pub message: String,
+    /// One result per plan that ran, in order
+    pub plans: Vec<SwapResult>,
+    /// The undo of the plans that had already run, when a failure stopped the batch
+    pub rollback: Option<SwapResult>,
+    /// Journal entry covering every plan, for undoing the whole batch with `undo_swap`
+    pub journal_entry_id: Option<String>,
+    pub elapsed_ms: u64,
+}
+
+impl BatchResult {
+    fn failed(message: impl Into<String>) -> Self {
+        BatchResult {
+            success: false,
+            message: message.into(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Payload of the `swap-complete` event emitted once a swap has finished
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwapCompleteEvent {
+    pub success: bool,
+    pub games_swapped: usize,
+    pub targets_affected: usize,
+    pub bytes_copied: u64,
+    pub elapsed_ms: u64,
+    pub warnings: usize,
+    pub errors: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppState {
     pub userdata_path: String,
     pub steam_path: String,
+    /// Folder under userdata that holds backups, see `set_backups_dir_name`
+    pub backups_dir_name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelectedSource {
+    pub id: String,
+    #[serde(rename = "isBackup")]
+    pub is_backup: bool,
+}
+
+/// Same shape the frontend writes under `swapConfiguration` in settings.json
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedSelection {
+    pub source: SelectedSource,
+    pub games: Vec<String>,
+    pub targets: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupScanEvent {
+    pub profile: Profile,
+    pub size_bytes: u64,
+    pub running_total_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CloudRisk {
+    pub target_id: String,
+    pub target_name: String,
+    pub game_id: String,
+    pub game_name: String,
+    /// The target has a remotecache.vdf for this game, so Steam Cloud may revert the swap
+    pub cloud_enabled: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CloudSummary {
+    pub profile_id: String,
+    /// Games with save data in the profile
+    pub total_games: usize,
+    /// Games with a remotecache.vdf, i.e. synced with Steam Cloud
+    pub cloud_games: usize,
+    pub local_games: usize,
+    /// Names of the cloud-synced games, sorted
+    pub cloud_game_names: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DedupeReport {
+    /// Backup game folders identical to a newer backup of the same game
+    pub duplicates_found: usize,
+    /// Duplicates whose files now share storage with the newer backup
+    pub duplicates_linked: usize,
+    pub bytes_reclaimed: u64,
+    pub details: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TargetSafety {
+    pub target_id: String,
+    pub target_name: String,
+    pub games: Vec<GameSafety>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameSafety {
+    pub game_id: String,
+    pub game_name: String,
+    /// The target has save data that the swap would overwrite
+    pub has_data: bool,
+    pub has_backup: bool,
+    /// The target's data changed after its last backup (always true without a backup), so
+    /// the swap's fresh backup is the only copy of those changes
+    pub newer_than_backup: bool,
+    pub cloud_enabled: bool,
+    pub running: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrphanedGame {
+    pub profile_id: String,
+    pub profile_name: String,
+    pub game_id: String,
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CloseSteamResult {
+    /// Processes Steam started that are still running after it exited, typically games
+    pub games_running: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeleteBackupResult {
+    pub success: bool,
+    pub message: String,
+    pub bytes_freed: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerifyReport {
+    pub ok: bool,
+    /// False for backups taken before manifests were written; nothing can be checked then
+    pub has_manifest: bool,
+    pub expected_file_count: usize,
+    pub actual_file_count: usize,
+    pub expected_size: u64,
+    pub actual_size: u64,
+    pub mismatches: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Overview {
+    pub profile_count: usize,
+    pub backup_count: usize,
+    pub total_games: usize,
+    pub total_save_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateStatus {
+    pub current_version: String,
+    pub update_available: bool,
+    pub version: Option<String>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiagnosticsReport {
+    pub os: String,
+    pub steam_path: Option<String>,
+    pub steamapps_dirs: Vec<String>,
+    pub appinfo_exists: bool,
+    pub appinfo_parsed: bool,
+    pub appinfo_entry_count: usize,
+    /// Set when appinfo.vdf couldn't be read and game names come from an older parse
+    pub appinfo_warning: Option<String>,
+    /// Low byte of the appinfo.vdf header magic (0x29 for v29), None when it couldn't be read
+    pub appinfo_version: Option<u32>,
+    pub userdata_exists: bool,
+    pub profile_count: usize,
 }
 
 // ─── AppInfo cache ──────────────────────────────────────────────────
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct CachedGameEntry {
     name: String,
     executables: Vec<String>,
@@ -65,38 +590,156 @@ struct AppInfoCache {
 
 static APP_INFO_CACHE: Mutex<Option<AppInfoCache>> = Mutex::new(None);
 
+/// Why the last `get_appinfo_games` call had to fall back to an older parse, if it did
+static APP_INFO_WARNING: Mutex<Option<String>> = Mutex::new(None);
+
+/// Where the parsed appinfo is persisted between launches; set once the app's cache dir is known
+static APP_INFO_DISK_CACHE: OnceLock<PathBuf> = OnceLock::new();
+
+const APP_INFO_CACHE_FILE: &str = "appinfo_cache.json";
+/// Bumped whenever the parsed entries change shape or meaning, so older caches are re-parsed
+const APP_INFO_CACHE_VERSION: u32 = 2;
+
+#[derive(Serialize, Deserialize)]
+struct PersistedAppInfoCache {
+    #[serde(default)]
+    version: u32,
+    appinfo_path: String,
+    /// appinfo.vdf mtime as nanoseconds since the Unix epoch
+    modified_nanos: u128,
+    games: HashMap<String, CachedGameEntry>,
+}
+
+fn system_time_nanos(time: SystemTime) -> Option<u128> {
+    time.duration_since(SystemTime::UNIX_EPOCH)
+        .ok()
+        .map(|d| d.as_nanos())
+}
+
+/// The persisted parse of `appinfo_path`; with `modified` set it must also match that mtime
+fn load_persisted_appinfo(
+    appinfo_path: &Path,
+    modified: Option<SystemTime>,
+) -> Option<HashMap<String, CachedGameEntry>> {
+    let cache_file = APP_INFO_DISK_CACHE.get()?;
+    let content = fs::read_to_string(cache_file).ok()?;
+    let persisted: PersistedAppInfoCache = serde_json::from_str(&content).ok()?;
+
+    let matches = persisted.version == APP_INFO_CACHE_VERSION
+        && persisted.appinfo_path == normalize_path(appinfo_path)
+        && modified.is_none_or(|m| Some(persisted.modified_nanos) == system_time_nanos(m));
+    matches.then_some(persisted.games)
+}
+
+fn persist_appinfo(
+    appinfo_path: &Path,
+    modified: SystemTime,
+    games: &HashMap<String, CachedGameEntry>,
+) {
+    let (Some(cache_file), Some(modified_nanos)) =
+        (APP_INFO_DISK_CACHE.get(), system_time_nanos(modified))
+    else {
+        return;
+    };
+
+    let persisted = PersistedAppInfoCache {
+        version: APP_INFO_CACHE_VERSION,
+        appinfo_path: normalize_path(appinfo_path),
+        modified_nanos,
+        games: games.clone(),
+    };
+    // Failing to write only costs a re-parse on the next launch
+    if let Ok(json) = serde_json::to_string(&persisted) {
+        if let Some(parent) = cache_file.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        let _ = fs::write(cache_file, json);
+    }
+}
+
+// ─── Settings store ─────────────────────────────────────────────────
+
+const SETTINGS_STORE: &str = "settings.json";
+const SELECTION_KEY: &str = "swapConfiguration";
+const BACKUPS_DIR_KEY: &str = "backupsDirName";
+const SWAP_STATS_KEY: &str = "swapStats";
+const TIME_PREFERENCE_KEY: &str = "timePreference";
+
+// ─── Backups folder ─────────────────────────────────────────────────
+
+const DEFAULT_BACKUPS_DIR: &str = "dunabackups";
+
+/// User-chosen backups folder name; None means `DEFAULT_BACKUPS_DIR`
+static BACKUPS_DIR_NAME: Mutex<Option<String>> = Mutex::new(None);
+
+fn backups_dir_name() -> String {
+    BACKUPS_DIR_NAME
+        .lock()
+        .unwrap()
+        .clone()
+        .unwrap_or_else(|| DEFAULT_BACKUPS_DIR.to_string())
+}
+
+fn backups_dir(userdata_path: &Path) -> PathBuf {
+    userdata_path.join(backups_dir_name())
+}
+
+/// A single folder name that can't be mistaken for an account folder
+fn validate_backups_dir_name(name: &str) -> Result<(), String> {
+    sanitize_component(name)?;
+    if name.chars().all(|c| c.is_ascii_digit()) {
+        return Err(
+            "The backups folder name can't be a number, it would look like a Steam account"
+                .to_string(),
+        );
+    }
+    Ok(())
+}
+
 // ─── Steam path detection ───────────────────────────────────────────
 
 #[cfg(target_os = "windows")]
 fn detect_steam_path() -> Option<PathBuf> {
-    use winreg::enums::HKEY_CURRENT_USER;
+    use winreg::enums::{HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE};
     use winreg::RegKey;
-    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
-    if let Ok(key) = hkcu.open_subkey("Software\\Valve\\Steam") {
-        if let Ok(path) = key.get_value::<String, _>("SteamPath") {
-            let p = PathBuf::from(&path);
-            if p.exists() {
-                return Some(p);
-            }
-        }
-    }
-    None
+    // HKCU is only written once the user has launched Steam; machine-wide installs record
+    // InstallPath under HKLM instead (WOW6432Node for the 32-bit installer)
+    let locations = [
+        (HKEY_CURRENT_USER, "Software\\Valve\\Steam", "SteamPath"),
+        (
+            HKEY_LOCAL_MACHINE,
+            "SOFTWARE\\WOW6432Node\\Valve\\Steam",
+            "InstallPath",
+        ),
+        (HKEY_LOCAL_MACHINE, "SOFTWARE\\Valve\\Steam", "InstallPath"),
+    ];
+    locations.iter().find_map(|(hive, subkey, value)| {
+        let path = RegKey::predef(*hive)
+            .open_subkey(subkey)
+            .and_then(|key| key.get_value::<String, _>(value))
+            .ok()?;
+        let p = PathBuf::from(path);
+        p.exists().then_some(p)
+    })
 }
 
 #[cfg(target_os = "linux")]
 fn detect_steam_path() -> Option<PathBuf> {
+    let mut candidates = Vec::new();
     if let Some(home) = std::env::var_os("HOME") {
         let home = PathBuf::from(home);
-        let p = home.join(".steam").join("steam");
-        if p.exists() {
-            return Some(p);
-        }
-        let p2 = home.join(".local/share/Steam");
-        if p2.exists() {
-            return Some(p2);
-        }
+        candidates.push(home.join(".steam").join("steam"));
+        candidates.push(home.join(".local/share/Steam"));
     }
-    None
+    // Steam Deck: the app may be started from a context where HOME isn't the deck user's
+    candidates.push(PathBuf::from("/home/deck/.local/share/Steam"));
+
+    // Prefer an install that actually has userdata over a leftover/empty Steam folder
+    candidates
+        .iter()
+        .find(|p| p.join("userdata").is_dir())
+        .or_else(|| candidates.iter().find(|p| p.exists()))
+        .cloned()
 }
 
 #[cfg(target_os = "macos")]
@@ -156,111 +799,402 @@ fn get_persona_name(userdata_path: &Path, user_id: &str) -> String {
     user_id.to_string()
 }
 
-fn normalize_path(path: &Path) -> String {
-    // Convert to string and normalize slashes to forward slashes
-    path.to_string_lossy().replace('\\', "/").to_string()
-}
+/// Reads the per-app `LastPlayed` values (unix seconds) from the `apps` sections of
+/// localconfig.vdf. Backups and profiles without a localconfig.vdf yield an empty map.
+fn get_last_played_times(profile_path: &Path) -> HashMap<String, u64> {
+    let mut last_played = HashMap::new();
+    let content = match fs::read_to_string(profile_path.join("config").join("localconfig.vdf")) {
+        Ok(c) => c,
+        Err(_) => return last_played,
+    };
 
-// ─── Steam library discovery ────────────────────────────────────────
+    for_each_vdf_value(&content, |sections, key, value| {
+        let in_app =
+            sections.len() >= 2 && sections[sections.len() - 2].eq_ignore_ascii_case("apps");
+        if in_app && key.eq_ignore_ascii_case("LastPlayed") {
+            if let Ok(secs) = value.parse::<u64>() {
+                last_played.insert(sections[sections.len() - 1].clone(), secs);
+            }
+        }
+    });
 
-fn find_all_steamapps_dirs(steam_path: &Path) -> Vec<PathBuf> {
-    let mut dirs = Vec::new();
-    let main_steamapps = steam_path.join("steamapps");
-    if main_steamapps.exists() {
-        dirs.push(main_steamapps.clone());
-    }
+    last_played
+}
 
-    // Parse libraryfolders.vdf to find additional library paths
-    let library_file = main_steamapps.join("libraryfolders.vdf");
-    if library_file.exists() {
-        if let Ok(content) = fs::read_to_string(&library_file) {
-            let re = regex::Regex::new(r#""path"\s+"([^"]+)""#).unwrap();
-            for captures in re.captures_iter(&content) {
-                if let Some(path_match) = captures.get(1) {
-                    let raw = path_match.as_str().replace("\\\\", "\\");
-                    let lib_path = PathBuf::from(&raw);
-                    let lib_steamapps = lib_path.join("steamapps");
-                    if lib_steamapps.exists() && !dirs.iter().any(|d| d == &lib_steamapps) {
-                        dirs.push(lib_steamapps);
-                    }
-                }
+/// Walks a text VDF file and calls `visit` for every key/value pair with the names of the
+/// sections it is nested in. Tokenizes just enough to know where a pair belongs.
+fn for_each_vdf_value(content: &str, mut visit: impl FnMut(&[String], &str, &str)) {
+    let token_re = regex::Regex::new(r#""((?:[^"\\]|\\.)*)"|\{|\}"#).unwrap();
+    let mut sections: Vec<String> = Vec::new();
+    let mut pending_key: Option<String> = None;
+    for token in token_re.captures_iter(content) {
+        match (token.get(1), &token[0]) {
+            (Some(text), _) => match pending_key.take() {
+                None => pending_key = Some(text.as_str().to_string()),
+                Some(key) => visit(&sections, &key, text.as_str()),
+            },
+            (None, "{") => sections.push(pending_key.take().unwrap_or_default()),
+            _ => {
+                sections.pop();
+                pending_key = None;
             }
         }
     }
-
-    dirs
 }
 
-fn get_appinfo_games(steam_path: &Path) -> HashMap<String, CachedGameEntry> {
-    let appinfo_path = steam_path.join("appcache").join("appinfo.vdf");
-    if !appinfo_path.exists() {
-        return HashMap::new();
-    }
+#[derive(Default)]
+struct LoginUser {
+    persona_name: Option<String>,
+    most_recent: bool,
+    timestamp: u64,
+}
 
-    let current_modified = fs::metadata(&appinfo_path)
-        .ok()
-        .and_then(|m| m.modified().ok());
+/// Entries of config/loginusers.vdf keyed by SteamID64
+fn read_login_users(steam_path: &Path) -> HashMap<String, LoginUser> {
+    let mut users: HashMap<String, LoginUser> = HashMap::new();
+    let Ok(content) = fs::read_to_string(steam_path.join("config").join("loginusers.vdf")) else {
+        return users;
+    };
 
-    // Check cache validity
-    {
-        let cache = APP_INFO_CACHE.lock().unwrap();
-        if let Some(ref c) = *cache {
-            let cache_valid = match (&c.last_modified, &current_modified) {
-                (Some(cached), Some(current)) => cached == current,
-                _ => false,
-            };
-            if cache_valid {
-                return c.games.clone();
-            }
+    for_each_vdf_value(&content, |sections, key, value| {
+        if sections.len() != 2 || !sections[0].eq_ignore_ascii_case("users") {
+            return;
         }
-    }
+        let user = users.entry(sections[1].clone()).or_default();
+        if key.eq_ignore_ascii_case("PersonaName") {
+            user.persona_name = Some(value.to_string());
+        } else if key.eq_ignore_ascii_case("MostRecent") {
+            user.most_recent = value == "1";
+        } else if key.eq_ignore_ascii_case("Timestamp") {
+            user.timestamp = value.parse().unwrap_or(0);
+        }
+    });
+    users
+}
 
-    // Parse the VDF file
-    let appinfo_vdf: Map<String, Value> = open_appinfo_vdf(&appinfo_path, Some(true));
+/// loginusers.vdf is keyed by SteamID64, userdata folders by the 32-bit account id
+fn to_account_id(steamid64: &str) -> Option<u64> {
+    steamid64.parse::<u64>().ok()?.checked_sub(STEAMID64_BASE)
+}
 
-    let mut games = HashMap::new();
+/// The account flagged `MostRecent` in config/loginusers.vdf, falling back to the newest
+/// `Timestamp` when no entry carries the flag
+fn find_current_user(steam_path: &Path) -> Option<CurrentUser> {
+    let (steamid64, user) = read_login_users(steam_path)
+        .into_iter()
+        .max_by_key(|(_, u)| (u.most_recent, u.timestamp))?;
+    let account_id = to_account_id(&steamid64)?;
 
-    if let Some(Value::Array(entries)) = appinfo_vdf.get("entries") {
-        for entry in entries {
-            let appid = match entry.get("appid") {
-                Some(Value::Number(n)) => n.to_string(),
-                _ => continue,
-            };
+    Some(CurrentUser {
+        id: account_id.to_string(),
+        persona_name: user.persona_name,
+    })
+}
 
-            let name = entry
-                .get("common")
-                .and_then(|c| c.get("name"))
-                .and_then(|n| n.as_str())
-                .unwrap_or("")
-                .to_string();
+// ─── Avatar lookup ──────────────────────────────────────────────────
 
-            if name.is_empty() {
-                continue;
-            }
+const STEAMID64_BASE: u64 = 76561197960265728;
 
-            let mut executables = Vec::new();
-            if let Some(launch) = entry.get("config").and_then(|c| c.get("launch")) {
-                if let Some(launch_map) = launch.as_object() {
-                    for (_, launch_config) in launch_map {
-                        if let Some(exe_path) =
-                            launch_config.get("executable").and_then(|e| e.as_str())
-                        {
-                            let normalized = exe_path.replace('\\', "/");
-                            if let Some(filename) = normalized.rsplit('/').next() {
-                                let filename = filename.to_string();
-                                if !filename.is_empty() && !executables.contains(&filename) {
-                                    executables.push(filename);
-                                }
-                            }
-                        }
-                    }
-                }
-            }
+/// Userdata folder of the offline / not-logged-in account
+const OFFLINE_ACCOUNT_ID: &str = "0";
 
-            games.insert(appid, CachedGameEntry { name, executables });
-        }
+fn to_steamid64(account_id: &str) -> Option<u64> {
+    account_id
+        .parse::<u64>()
+        .ok()
+        .filter(|&id| id != 0)
+        .map(|id| id + STEAMID64_BASE)
+}
+
+fn find_avatar_path(steam_path: &Path, userdata_path: &Path, user_id: &str) -> Option<String> {
+    let steamid64 = to_steamid64(user_id)?;
+    let file_name = format!("{}.png", steamid64);
+
+    // Steam keeps a global avatar cache, some versions also store one per user
+    let candidates = [
+        steam_path
+            .join("config")
+            .join("avatarcache")
+            .join(&file_name),
+        userdata_path
+            .join(user_id)
+            .join("config")
+            .join("avatarcache")
+            .join(&file_name),
+    ];
+
+    candidates
+        .iter()
+        .find(|p| p.is_file())
+        .map(|p| normalize_path(p))
+}
+
+// ─── Path safety ────────────────────────────────────────────────────
+
+/// Rejects ids that could escape the directory they are joined onto (`..`, separators,
+/// drive prefixes)
+fn sanitize_component(value: &str) -> Result<&str, String> {
+    let invalid =
+        value.is_empty() || value == "." || value == ".." || value.contains(['/', '\\', ':', '\0']);
+    if invalid {
+        return Err(format!("Invalid path component: {:?}", value));
+    }
+    Ok(value)
+}
+
+/// Account and game ids are always decimal numbers
+fn sanitize_id(value: &str) -> Result<&str, String> {
+    let value = sanitize_component(value)?;
+    if !value.chars().all(|c| c.is_ascii_digit()) {
+        return Err(format!("Invalid id: {:?}", value));
+    }
+    Ok(value)
+}
+
+fn sanitize_ids<'a>(ids: impl IntoIterator<Item = &'a String>) -> Result<(), String> {
+    ids.into_iter()
+        .try_for_each(|id| sanitize_id(id).map(|_| ()))
+}
+
+/// Like `sanitize_ids`, also allowing `ALL_GAMES` for commands that resolve it with
+/// `resolve_game_selection`
+fn sanitize_game_ids(game_ids: &[String]) -> Result<(), String> {
+    sanitize_ids(game_ids.iter().filter(|id| id.as_str() != ALL_GAMES))
+}
+
+/// Backup ids are `<account id>/<snapshot>`, see `BackupSnapshot`. Returns both parts.
+fn sanitize_backup_id(value: &str) -> Result<(&str, &str), String> {
+    let invalid = || format!("Invalid backup id: {:?}", value);
+    let (account_id, snapshot) = value.split_once('/').ok_or_else(invalid)?;
+    sanitize_id(account_id)?;
+    sanitize_component(snapshot)?;
+    parse_snapshot_name(snapshot).ok_or_else(invalid)?;
+    Ok((account_id, snapshot))
+}
+
+fn sanitize_profile_id(value: &str, is_backup: bool) -> Result<(), String> {
+    if is_backup {
+        sanitize_backup_id(value).map(|_| ())
+    } else {
+        sanitize_id(value).map(|_| ())
+    }
+}
+
+/// Relative path that must stay inside the directory it is joined onto; every component is
+/// checked like an id component
+fn sanitize_relative_path(value: &str) -> Result<(), String> {
+    value
+        .split(['/', '\\'])
+        .try_for_each(|part| sanitize_component(part).map(|_| ()))
+        .map_err(|_| format!("Invalid relative path: {:?}", value))
+}
+
+fn normalize_path(path: &Path) -> String {
+    // Convert to string and normalize slashes to forward slashes
+    path.to_string_lossy().replace('\\', "/").to_string()
+}
+
+// ─── Steam library discovery ────────────────────────────────────────
+
+/// Extracts library roots from libraryfolders.vdf. Current Steam writes nested
+/// `"0" { "path" "..." }` blocks, older versions wrote `"1" "D:\\SteamLibrary"` directly.
+fn parse_library_folder_paths(content: &str) -> Vec<PathBuf> {
+    let re = regex::Regex::new(r#"(?m)^\s*"(path|\d+)"[ \t]+"([^"]+)""#).unwrap();
+    re.captures_iter(content)
+        .filter_map(|c| Some((c.get(1)?.as_str(), c.get(2)?.as_str())))
+        // Numeric keys also appear in the nested "apps" blocks (appid → size), only take paths
+        .filter(|(key, value)| *key == "path" || value.contains(['/', '\\']))
+        .map(|(_, value)| PathBuf::from(value.replace("\\\\", "\\")))
+        .collect()
+}
+
+/// Whether two paths name the same folder, links resolved when both exist
+fn same_folder(a: &Path, b: &Path) -> bool {
+    match (fs::canonicalize(a), fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => library_dir_key(a) == library_dir_key(b),
+    }
+}
+
+fn library_dir_key(path: &Path) -> String {
+    let key = normalize_path(path).trim_end_matches('/').to_string();
+    // NTFS paths are case-insensitive, so C:/Steam and c:/steam are the same library
+    if cfg!(windows) {
+        key.to_lowercase()
+    } else {
+        key
+    }
+}
+
+fn find_all_steamapps_dirs(steam_path: &Path) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    let main_steamapps = steam_path.join("steamapps");
+    if main_steamapps.exists() {
+        dirs.push(main_steamapps.clone());
+    }
+
+    // Parse libraryfolders.vdf to find additional library paths
+    add_libraries_from_vdf(&main_steamapps, &mut dirs);
+
+    for media_steamapps in removable_media_steamapps_dirs() {
+        add_library_dir(media_steamapps.clone(), &mut dirs);
+        add_libraries_from_vdf(&media_steamapps, &mut dirs);
+    }
+
+    dirs
+}
+
+fn add_libraries_from_vdf(steamapps: &Path, dirs: &mut Vec<PathBuf>) {
+    let library_file = steamapps.join("libraryfolders.vdf");
+    if let Ok(content) = fs::read_to_string(&library_file) {
+        for lib_path in parse_library_folder_paths(&content) {
+            add_library_dir(lib_path.join("steamapps"), dirs);
+        }
+    }
+}
+
+fn add_library_dir(lib_steamapps: PathBuf, dirs: &mut Vec<PathBuf>) {
+    // Libraries on offline drives don't exist, and a drive letter that is mounted but
+    // empty (or an unreachable network share) may exist yet not be listable
+    if !lib_steamapps.is_dir() || fs::read_dir(&lib_steamapps).is_err() {
+        return;
+    }
+
+    let key = library_dir_key(&lib_steamapps);
+    if !dirs.iter().any(|d| library_dir_key(d) == key) {
+        dirs.push(lib_steamapps);
+    }
+}
+
+/// Steam libraries on mounted removable media, e.g. a Steam Deck's microSD card. Depending on
+/// the SteamOS version the card is mounted at `/run/media/<label>` or `/run/media/<user>/<label>`.
+#[cfg(target_os = "linux")]
+fn removable_media_steamapps_dirs() -> Vec<PathBuf> {
+    let media_root = Path::new("/run/media");
+    let Ok(entries) = fs::read_dir(media_root) else {
+        return vec![];
+    };
+
+    let mut mounts = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_dir() {
+            continue;
+        }
+        if let Ok(children) = fs::read_dir(&path) {
+            mounts.extend(children.flatten().map(|c| c.path()).filter(|p| p.is_dir()));
+        }
+        mounts.push(path);
+    }
+
+    mounts
+        .iter()
+        .flat_map(|m| {
+            [
+                m.join("steamapps"),
+                m.join("SteamLibrary").join("steamapps"),
+            ]
+        })
+        .filter(|p| p.is_dir())
+        .collect()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn removable_media_steamapps_dirs() -> Vec<PathBuf> {
+    vec![]
+}
+
+fn get_appinfo_games(steam_path: &Path) -> HashMap<String, CachedGameEntry> {
+    let appinfo_path = steam_path.join("appcache").join("appinfo.vdf");
+    if !appinfo_path.exists() {
+        return HashMap::new();
+    }
+
+    let current_modified = fs::metadata(&appinfo_path)
+        .ok()
+        .and_then(|m| m.modified().ok());
+
+    // Check cache validity
+    {
+        let cache = APP_INFO_CACHE.lock().unwrap();
+        if let Some(ref c) = *cache {
+            let cache_valid = match (&c.last_modified, &current_modified) {
+                (Some(cached), Some(current)) => cached == current,
+                _ => false,
+            };
+            if cache_valid {
+                return c.games.clone();
+            }
+        }
+    }
+
+    // Fresh launch: reuse the parse from a previous run if appinfo.vdf hasn't changed since
+    if let Some(modified) = current_modified {
+        if let Some(games) = load_persisted_appinfo(&appinfo_path, Some(modified)) {
+            let mut cache = APP_INFO_CACHE.lock().unwrap();
+            *cache = Some(AppInfoCache {
+                last_modified: current_modified,
+                games: games.clone(),
+            });
+            log::info!("Loaded {} appinfo entries from the disk cache", games.len());
+            return games;
+        }
+    }
+
+    // Steam can hold the file open exclusively on Windows while running, which would otherwise
+    // leave every profile without games
+    parse_appinfo_games(&appinfo_path, current_modified)
+        .unwrap_or_else(|e| stale_appinfo_games(&appinfo_path, e))
+}
+
+/// Parses appinfo.vdf and, only once that succeeded, replaces the in-memory and on-disk caches
+/// with the result
+fn parse_appinfo_games(
+    appinfo_path: &Path,
+    current_modified: Option<SystemTime>,
+) -> Result<HashMap<String, CachedGameEntry>, String> {
+    let parse_started = Instant::now();
+    let appinfo_vdf = read_appinfo_vdf(appinfo_path)?;
+    *APP_INFO_WARNING.lock().unwrap() = None;
+
+    let mut games = HashMap::new();
+
+    if let Some(Value::Array(entries)) = appinfo_vdf.get("entries") {
+        for entry in entries {
+            let appid = match entry.get("appid") {
+                Some(Value::Number(n)) => n.to_string(),
+                _ => continue,
+            };
+
+            let name = entry
+                .get("common")
+                .and_then(|c| c.get("name"))
+                .and_then(|n| n.as_str())
+                .unwrap_or("")
+                .to_string();
+
+            if name.is_empty() {
+                continue;
+            }
+
+            let executables = entry
+                .get("config")
+                .and_then(|c| c.get("launch"))
+                .and_then(|l| l.as_object())
+                .map(launch_executables)
+                .unwrap_or_default();
+
+            games.insert(appid, CachedGameEntry { name, executables });
+        }
     }
 
+    log::info!(
+        "Parsed {} appinfo entries from {} in {} ms",
+        games.len(),
+        appinfo_path.display(),
+        parse_started.elapsed().as_millis()
+    );
+
     // Update cache
     {
         let mut cache = APP_INFO_CACHE.lock().unwrap();
@@ -269,10 +1203,188 @@ fn get_appinfo_games(steam_path: &Path) -> HashMap<String, CachedGameEntry> {
             games: games.clone(),
         });
     }
+    if let Some(modified) = current_modified {
+        persist_appinfo(appinfo_path, modified, &games);
+    }
+
+    Ok(games)
+}
+
+/// Steam's name for the platform we run on, as used in launch entries' `oslist`
+const CURRENT_OS: &str = if cfg!(target_os = "windows") {
+    "windows"
+} else if cfg!(target_os = "macos") {
+    "macos"
+} else {
+    "linux"
+};
+
+/// Executable file names from an app's launch entries, those for the current platform first.
+/// Entries for other platforms are dropped unless nothing else is left; on Linux Windows
+/// entries are kept since Proton runs them under their own name.
+fn launch_executables(launch: &Map<String, Value>) -> Vec<String> {
+    let mut ranked: Vec<(u8, String)> = Vec::new();
+    for launch_config in launch.values() {
+        let Some(exe_path) = launch_config.get("executable").and_then(|e| e.as_str()) else {
+            continue;
+        };
+        let normalized = exe_path.replace('\\', "/");
+        let filename = normalized.rsplit('/').next().unwrap_or("").to_string();
+        if filename.is_empty() {
+            continue;
+        }
+
+        let oslist = launch_config
+            .get("config")
+            .and_then(|c| c.get("oslist"))
+            .and_then(|o| o.as_str())
+            .unwrap_or("");
+        let rank = if oslist.is_empty() {
+            1
+        } else if oslist.split(',').any(|os| os.trim() == CURRENT_OS) {
+            0
+        } else if CURRENT_OS == "linux" && oslist.split(',').any(|os| os.trim() == "windows") {
+            1
+        } else {
+            2
+        };
+        ranked.push((rank, filename));
+    }
+
+    let best = ranked.iter().map(|(rank, _)| *rank).min().unwrap_or(0);
+    ranked.sort_by_key(|(rank, _)| *rank);
+    let mut executables = Vec::new();
+    for (rank, filename) in ranked {
+        if (rank < 2 || best == 2) && !executables.contains(&filename) {
+            executables.push(filename);
+        }
+    }
+    executables
+}
+
+/// The executable names `check_games_running` looks for, to verify detection for a game
+#[tauri::command]
+fn game_executables(steam_path: String, game_id: String) -> Result<Vec<String>, String> {
+    sanitize_id(&game_id)?;
+    let steam = Path::new(&steam_path);
+    let appinfo_games = get_appinfo_games(steam);
+    let steamapps_dirs = find_all_steamapps_dirs(steam);
+    Ok(get_game_info(&appinfo_games, &steamapps_dirs, &game_id)
+        .map(|(_, executables)| executables)
+        .unwrap_or_default())
+}
+
+/// The game's Proton prefix (`steamapps/compatdata/<appid>`) in whichever library has one, where
+/// Windows games run through Proton keep saves that never reach userdata. The prefix is shared
+/// by every account on the machine, so it can be inspected or backed up but not swapped between
+/// profiles.
+#[tauri::command]
+fn get_proton_saves(steam_path: String, game_id: String) -> Option<String> {
+    sanitize_id(&game_id).ok()?;
+    find_all_steamapps_dirs(Path::new(&steam_path))
+        .into_iter()
+        .map(|dir| dir.join("compatdata").join(&game_id))
+        .find(|prefix| prefix.join("pfx").is_dir())
+        .map(|prefix| normalize_path(&prefix))
+}
+
+/// A game's name from appinfo or its appmanifest, for ids the frontend has without a profile
+#[tauri::command]
+fn resolve_game_name(steam_path: String, game_id: String) -> Option<String> {
+    sanitize_id(&game_id).ok()?;
+    let steam = Path::new(&steam_path);
+    let appinfo_games = get_appinfo_games(steam);
+    let steamapps_dirs = find_all_steamapps_dirs(steam);
+    get_game_info(&appinfo_games, &steamapps_dirs, &game_id).map(|(name, _)| name)
+}
+
+/// appinfo.vdf starts with a little-endian magic whose low byte is the format version, written
+/// in hex (v29 is `0x07564429`). These are the versions the parser understands (v29 added the
+/// key table for the "universe" format).
+const SUPPORTED_APPINFO_VERSIONS: [u32; 3] = [0x27, 0x28, 0x29];
+const APPINFO_MAGIC_BASE: u32 = 0x0756_4400;
+
+/// Reads the format version from the file header
+fn appinfo_version(appinfo_path: &Path) -> Result<u32, String> {
+    let mut header = [0u8; 4];
+    let mut file = fs::File::open(appinfo_path).map_err(|e| e.to_string())?;
+    io::Read::read_exact(&mut file, &mut header).map_err(|e| e.to_string())?;
+
+    let magic = u32::from_le_bytes(header);
+    if magic & 0xFFFF_FF00 != APPINFO_MAGIC_BASE {
+        return Err(format!("unrecognized header 0x{:08x}", magic));
+    }
+    Ok(magic & 0xFF)
+}
+
+/// Opens appinfo.vdf ourselves first, so a locked file or an unknown format version is an
+/// error here rather than a panic or an empty map from the parser
+fn read_appinfo_vdf(appinfo_path: &Path) -> Result<Map<String, Value>, String> {
+    let version = appinfo_version(appinfo_path)?;
+    if !SUPPORTED_APPINFO_VERSIONS.contains(&version) {
+        return Err(format!("unsupported format version {:x}", version));
+    }
+
+    let vdf = std::panic::catch_unwind(|| open_appinfo_vdf(appinfo_path, Some(true)))
+        .map_err(|_| "the file could not be parsed".to_string())?;
+    if !matches!(vdf.get("entries"), Some(Value::Array(entries)) if !entries.is_empty()) {
+        return Err("the file has no app entries".to_string());
+    }
+    Ok(vdf)
+}
 
+/// The last successful parse, from memory or the disk cache, whatever appinfo.vdf it was
+/// made from. Not stored as the current cache, so the next call tries the file again.
+fn stale_appinfo_games(appinfo_path: &Path, error: String) -> HashMap<String, CachedGameEntry> {
+    let games = APP_INFO_CACHE
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|c| c.games.clone())
+        .or_else(|| load_persisted_appinfo(appinfo_path, None))
+        .unwrap_or_default();
+
+    let warning = if games.is_empty() {
+        format!(
+            "Could not read {} ({}), only installed games can be named until it can be read",
+            appinfo_path.display(),
+            error
+        )
+    } else {
+        format!(
+            "Could not read {} ({}), game names come from an earlier read and may be stale",
+            appinfo_path.display(),
+            error
+        )
+    };
+    log::warn!("{}", warning);
+    *APP_INFO_WARNING.lock().unwrap() = Some(warning);
     games
 }
 
+/// Parses appinfo.vdf again whatever the caches say, for when the mtime check misses an
+/// update. The caches are only replaced by a successful parse, so a failed one still falls back
+/// to them. Returns the number of games loaded.
+#[tauri::command]
+fn refresh_appinfo_cache(steam_path: String) -> usize {
+    let appinfo_path = Path::new(&steam_path).join("appcache").join("appinfo.vdf");
+    if !appinfo_path.exists() {
+        return 0;
+    }
+    let current_modified = fs::metadata(&appinfo_path)
+        .ok()
+        .and_then(|m| m.modified().ok());
+    parse_appinfo_games(&appinfo_path, current_modified)
+        .unwrap_or_else(|e| stale_appinfo_games(&appinfo_path, e))
+        .len()
+}
+
+/// Set when game names couldn't be read fresh from appinfo.vdf (e.g. Steam has it locked)
+#[tauri::command]
+fn appinfo_warning() -> Option<String> {
+    APP_INFO_WARNING.lock().unwrap().clone()
+}
+
 fn get_game_name_from_manifest(steamapps_dirs: &[PathBuf], game_id: &str) -> Option<String> {
     let manifest_name = format!("appmanifest_{}.acf", game_id);
     for dir in steamapps_dirs {
@@ -294,6 +1406,21 @@ fn get_game_name_from_manifest(steamapps_dirs: &[PathBuf], game_id: &str) -> Opt
     None
 }
 
+/// `<library>/steamapps/common/<installdir>` from the game's appmanifest, canonicalized so it
+/// can be compared with the paths of running executables
+fn game_install_dir(steam_path: &Path, game_id: &str) -> Option<PathBuf> {
+    let manifest_name = format!("appmanifest_{}.acf", game_id);
+    let re = regex::Regex::new(r#""installdir"\s+"([^"]+)""#).unwrap();
+    find_all_steamapps_dirs(steam_path).iter().find_map(|dir| {
+        let content = fs::read_to_string(dir.join(&manifest_name)).ok()?;
+        let install_dir = re.captures(&content)?.get(1)?.as_str().trim();
+        if install_dir.is_empty() {
+            return None;
+        }
+        dir.join("common").join(install_dir).canonicalize().ok()
+    })
+}
+
 fn get_game_info(
     appinfo_games: &HashMap<String, CachedGameEntry>,
     steamapps_dirs: &[PathBuf],
@@ -312,53 +1439,162 @@ fn get_game_info(
     None
 }
 
+/// Steam Cloud's per-game sync state, kept next to the save files
+const REMOTECACHE_FILE: &str = "remotecache.vdf";
+
+fn is_remotecache(path: &Path) -> bool {
+    path.file_name()
+        .map(|n| n.to_string_lossy().eq_ignore_ascii_case(REMOTECACHE_FILE))
+        .unwrap_or(false)
+        && path.is_file()
+}
+
+/// How deep `has_meaningful_game_data` looks for a non-empty file. Folders at that depth
+/// count as data without being read.
+const MEANINGFUL_DATA_MAX_DEPTH: usize = 6;
+
+/// Whether the folder holds at least one non-empty file besides remotecache.vdf. Games often
+/// leave an empty `remote` folder behind, which doesn't count.
 fn has_meaningful_game_data(game_path: &Path) -> bool {
-    let entries = match fs::read_dir(game_path) {
-        Ok(e) => e,
-        Err(_) => return false,
-    };
+    WalkDir::new(game_path)
+        .min_depth(1)
+        .max_depth(MEANINGFUL_DATA_MAX_DEPTH)
+        .into_iter()
+        .flatten()
+        .any(|entry| {
+            let file_type = entry.file_type();
+            if file_type.is_dir() {
+                return entry.depth() == MEANINGFUL_DATA_MAX_DEPTH;
+            }
+            // Links aren't followed; whatever they point to is treated as data
+            file_type.is_symlink()
+                || (!is_remotecache(entry.path())
+                    && entry.metadata().map(|m| m.len() > 0).unwrap_or(false))
+        })
+}
+
+/// The folder's name if it is a numeric account or game id. Names that aren't valid UTF-8
+/// can't be ids, so they are rejected here rather than lossily converted.
+fn numeric_folder_name(path: &Path) -> Option<String> {
+    let name = path.file_name()?.to_str()?;
+    let numeric = !name.is_empty() && name.chars().all(|c| c.is_ascii_digit());
+    numeric.then(|| name.to_string())
+}
 
+fn has_remotecache(game_path: &Path) -> bool {
+    fs::read_dir(game_path)
+        .map(|entries| entries.flatten().any(|e| is_remotecache(&e.path())))
+        .unwrap_or(false)
+}
+
+fn count_profile_games(
+    profile_path: &Path,
+    appinfo_games: &HashMap<String, CachedGameEntry>,
+    steamapps_dirs: &[PathBuf],
+) -> io::Result<usize> {
+    let mut count = 0;
+    let entries = fs::read_dir(profile_path)?;
     for entry in entries.flatten() {
         let path = entry.path();
-        if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-            if name.eq_ignore_ascii_case("remotecache.vdf") && path.is_file() {
+        if !path.is_dir() {
+            continue;
+        }
+        let Some(folder_name) = numeric_folder_name(&path) else {
+            continue;
+        };
+        if !has_meaningful_game_data(&path) {
+            continue;
+        }
+        if get_game_info(appinfo_games, steamapps_dirs, &folder_name).is_some() {
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+/// Backup ids are split into their account and snapshot folders
+fn profile_base_path(userdata_path: &Path, profile_id: &str, is_backup: bool) -> PathBuf {
+    if is_backup {
+        profile_id
+            .split('/')
+            .fold(backups_dir(userdata_path), |path, part| path.join(part))
+    } else {
+        userdata_path.join(profile_id)
+    }
+}
+
+/// Passing this as the only game id selects every game folder the source has data for
+const ALL_GAMES: &str = "*";
+
+fn list_game_folder_ids(profile_path: &Path) -> Vec<String> {
+    let mut ids = Vec::new();
+    if let Ok(entries) = fs::read_dir(profile_path) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if !path.is_dir() {
                 continue;
             }
+            if let Some(folder_name) = numeric_folder_name(&path) {
+                if has_meaningful_game_data(&path) {
+                    ids.push(folder_name);
+                }
+            }
         }
-        return true;
     }
+    ids.sort();
+    ids
+}
 
-    false
+fn resolve_game_selection(
+    source_base: &Path,
+    game_ids: &[String],
+    exclude_game_ids: &[String],
+) -> Vec<String> {
+    let selected = if game_ids.iter().any(|g| g == ALL_GAMES) {
+        list_game_folder_ids(source_base)
+    } else {
+        game_ids.to_vec()
+    };
+
+    selected
+        .into_iter()
+        .filter(|g| !exclude_game_ids.contains(g))
+        .collect()
 }
 
-fn count_profile_games(
+fn list_profile_games(
     profile_path: &Path,
     appinfo_games: &HashMap<String, CachedGameEntry>,
     steamapps_dirs: &[PathBuf],
-) -> usize {
-    let mut count = 0;
+) -> Vec<GameInfo> {
+    let mut games = Vec::new();
+    let last_played = get_last_played_times(profile_path);
     if let Ok(entries) = fs::read_dir(profile_path) {
         for entry in entries.flatten() {
             let path = entry.path();
             if !path.is_dir() {
                 continue;
             }
-            let folder_name = match path.file_name() {
-                Some(n) => n.to_string_lossy().to_string(),
-                None => continue,
-            };
-            if !folder_name.chars().all(|c| c.is_ascii_digit()) {
+            let Some(folder_name) = numeric_folder_name(&path) else {
                 continue;
-            }
+            };
             if !has_meaningful_game_data(&path) {
                 continue;
             }
-            if get_game_info(appinfo_games, steamapps_dirs, &folder_name).is_some() {
-                count += 1;
-            }
-        }
+            if let Some((name, _)) = get_game_info(appinfo_games, steamapps_dirs, &folder_name) {
+                games.push(GameInfo {
+                    last_played: format_timestamp(
+                        last_played.get(&folder_name).copied().unwrap_or(0),
+                    ),
+                    id: folder_name,
+                    name,
+                });
+            }
+        }
     }
-    count
+
+    games.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+    games
 }
 
 // ─── Profile discovery ──────────────────────────────────────────────
@@ -366,6 +1602,7 @@ fn count_profile_games(
 fn discover_profiles(userdata_path: &Path, steam_path: &Path, steamapps_dirs: &[PathBuf]) -> Vec<Profile> {
     let mut profiles = Vec::new();
     let appinfo_games = get_appinfo_games(steam_path);
+    let drives = Drives::list();
 
     if !userdata_path.exists() {
         return profiles;
@@ -382,116 +1619,281 @@ fn discover_profiles(userdata_path: &Path, steam_path: &Path, steamapps_dirs: &[
             continue;
         }
 
-        let folder_name = match path.file_name() {
-            Some(n) => n.to_string_lossy().to_string(),
-            None => continue,
-        };
-
-        // Skip dunabackups folder
-        if folder_name == "dunabackups" {
-            continue;
+        if let Some(profile) = discover_regular_profile(
+            &path,
+            userdata_path,
+            steam_path,
+            &appinfo_games,
+            steamapps_dirs,
+            &drives,
+        ) {
+            profiles.push(profile);
         }
+    }
 
-        // Skip non-numeric folders (not user IDs)
-        if !folder_name.chars().all(|c| c.is_ascii_digit()) {
-            continue;
+    // Also discover backup profiles, one per snapshot
+    for snapshot in list_backup_snapshots(userdata_path) {
+        if let Some(profile) = discover_backup_profile(
+            &snapshot,
+            userdata_path,
+            steam_path,
+            &appinfo_games,
+            steamapps_dirs,
+            &drives,
+        ) {
+            profiles.push(profile);
         }
+    }
 
-        // Must have config/localconfig.vdf - this is mandatory
-        let has_config = path.join("config").join("localconfig.vdf").exists();
-        if !has_config {
-            continue;
+    // Sort profiles: regular profiles first, then backups, each sorted by last login (most recent first)
+    sort_profiles(&mut profiles, ProfileSort::LastLogin);
+
+    profiles
+}
+
+/// Reorders profiles by `sort`, always keeping regular profiles ahead of backups
+fn sort_profiles(profiles: &mut [Profile], sort: ProfileSort) {
+    profiles.sort_by(|a, b| {
+        // false < true, so regular profiles come first
+        a.is_backup.cmp(&b.is_backup).then_with(|| match sort {
+            ProfileSort::LastLogin => b.last_login_secs.cmp(&a.last_login_secs),
+            ProfileSort::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+            ProfileSort::GameCount => b
+                .game_count
+                .cmp(&a.game_count)
+                .then_with(|| b.last_login_secs.cmp(&a.last_login_secs)),
+        })
+    });
+}
+
+fn discover_regular_profile(
+    path: &Path,
+    userdata_path: &Path,
+    steam_path: &Path,
+    appinfo_games: &HashMap<String, CachedGameEntry>,
+    steamapps_dirs: &[PathBuf],
+    drives: &Drives,
+) -> Option<Profile> {
+    // Only numeric folders are accounts. This skips the backups folder, `anonymous` (written
+    // by some Steam builds before login) and `ac` (anti-cheat data), none of which hold saves
+    // we could swap. The `0` offline account is numeric and kept, flagged as such.
+    let folder_name = numeric_folder_name(path)?;
+
+    // An unreadable folder is still listed so the UI can say why it looks empty
+    let (game_count, read_error) = match count_profile_games(path, appinfo_games, steamapps_dirs) {
+        Ok(count) => (count, None),
+        Err(e) => {
+            log::warn!("Could not read profile folder {}: {}", path.display(), e);
+            (0, Some(e.to_string()))
         }
+    };
 
-        let game_count = count_profile_games(&path, &appinfo_games, steamapps_dirs);
-        let name = get_persona_name(userdata_path, &folder_name);
-        
-        // Get last login time from localconfig.vdf modification date
-        let last_login = path
-            .join("config")
+    // Without config/localconfig.vdf we only list the account if it actually holds game data
+    let has_local_config = path.join("config").join("localconfig.vdf").exists();
+    if !has_local_config && game_count == 0 && read_error.is_none() {
+        return None;
+    }
+
+    let name = get_persona_name(userdata_path, &folder_name);
+    let avatar_path = find_avatar_path(steam_path, userdata_path, &folder_name);
+
+    // Get last login time from localconfig.vdf modification date, or the newest file otherwise
+    let last_login = if has_local_config {
+        path.join("config")
             .join("localconfig.vdf")
             .metadata()
             .and_then(|m| m.modified())
             .ok()
             .and_then(|t| t.duration_since(SystemTime::UNIX_EPOCH).ok())
             .map(|d| d.as_secs())
-            .unwrap_or(0);
+            .unwrap_or(0)
+    } else {
+        get_latest_modified_time(path)
+    };
 
-        profiles.push(Profile {
-            id: folder_name,
-            name,
-            game_count,
-            is_backup: false,
-            path: normalize_path(&path),
-            last_login: format_timestamp(last_login),
-        });
-    }
+    Some(Profile {
+        name,
+        game_count,
+        is_backup: false,
+        path: normalize_path(path),
+        last_login: format_timestamp(last_login),
+        last_login_secs: last_login,
+        steamid64: to_steamid64(&folder_name).map(|id| id.to_string()),
+        is_offline_account: folder_name == OFFLINE_ACCOUNT_ID,
+        drive: drive_label(path, drives),
+        total_size_bytes: 0,
+        total_size: String::new(),
+        id: folder_name,
+        avatar_path,
+        has_local_config,
+        read_error,
+    })
+}
 
-    // Also discover backup profiles
-    let backups_dir = userdata_path.join("dunabackups");
-    if backups_dir.exists() {
-        if let Ok(entries) = fs::read_dir(&backups_dir) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if !path.is_dir() {
-                    continue;
-                }
-                let folder_name = match path.file_name() {
-                    Some(n) => n.to_string_lossy().to_string(),
-                    None => continue,
-                };
+fn list_backup_dirs(userdata_path: &Path) -> Vec<PathBuf> {
+    let backups_dir = backups_dir(userdata_path);
+    match fs::read_dir(&backups_dir) {
+        Ok(entries) => entries
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.is_dir())
+            .collect(),
+        Err(_) => vec![],
+    }
+}
 
-                let game_count = count_profile_games(&path, &appinfo_games, steamapps_dirs);
+fn discover_backup_profile(
+    snapshot: &BackupSnapshot,
+    userdata_path: &Path,
+    steam_path: &Path,
+    appinfo_games: &HashMap<String, CachedGameEntry>,
+    steamapps_dirs: &[PathBuf],
+    drives: &Drives,
+) -> Option<Profile> {
+    let path = snapshot.path.as_path();
+    let folder_name = &snapshot.account_id;
 
-                let name = get_persona_name(userdata_path, &folder_name);
-                let display_name = if name == folder_name {
-                    format!("Backup - {}", folder_name)
-                } else {
-                    format!("Backup - {}", name)
-                };
-                
-                // For backups, get the latest modification time from any file in the backup folder
-                let last_login = get_latest_modified_time(&path);
-
-                if game_count > 0 {
-                    profiles.push(Profile {
-                        id: folder_name,
-                        name: display_name,
-                        game_count,
-                        is_backup: true,
-                        path: normalize_path(&path),
-                        last_login: format_timestamp(last_login),
-                    });
-                }
-            }
+    let (game_count, read_error) = match count_profile_games(path, appinfo_games, steamapps_dirs) {
+        Ok(0) => return None,
+        Ok(count) => (count, None),
+        Err(e) => {
+            log::warn!("Could not read backup folder {}: {}", path.display(), e);
+            (0, Some(e.to_string()))
         }
-    }
+    };
 
-    // Sort profiles: regular profiles first, then backups, each sorted by last login (most recent first)
-    profiles.sort_by(|a, b| {
-        // First compare by backup status (false < true, so regular profiles come first)
-        match a.is_backup.cmp(&b.is_backup) {
-            std::cmp::Ordering::Equal => {
-                // Within the same group, sort by last login (most recent first)
-                b.last_login.cmp(&a.last_login)
-            }
-            other => other,
-        }
-    });
+    let name = get_persona_name(userdata_path, folder_name);
+    let display_name = match &snapshot.label {
+        Some(label) => format!("Backup - {} ({})", name, label),
+        None => format!("Backup - {}", name),
+    };
 
-    profiles
+    // For backups, the time the snapshot was taken
+    let last_login = snapshot.created;
+
+    let avatar_path = find_avatar_path(steam_path, userdata_path, folder_name);
+    let has_local_config = userdata_path
+        .join(folder_name)
+        .join("config")
+        .join("localconfig.vdf")
+        .exists();
+
+    Some(Profile {
+        name: display_name,
+        game_count,
+        is_backup: true,
+        path: normalize_path(path),
+        last_login: format_timestamp(last_login),
+        last_login_secs: last_login,
+        steamid64: to_steamid64(folder_name).map(|id| id.to_string()),
+        is_offline_account: folder_name == OFFLINE_ACCOUNT_ID,
+        drive: drive_label(path, drives),
+        total_size_bytes: 0,
+        total_size: String::new(),
+        id: snapshot.id(),
+        avatar_path,
+        has_local_config,
+        read_error,
+    })
+}
+
+// ─── Backup labels ──────────────────────────────────────────────────
+
+/// Labels are part of the snapshot's folder name (see `snapshot_name`). Single slot backups
+/// kept theirs in this file, which is folded into the name when they are migrated.
+const LEGACY_BACKUP_LABEL_FILE: &str = ".label";
+const MAX_BACKUP_LABEL_LEN: usize = 64;
+
+/// Keeps letters, digits, spaces and `-_.()`, capped at `MAX_BACKUP_LABEL_LEN` characters.
+/// Trailing dots go as well, Windows drops them from folder names. Returns `None` when nothing
+/// is left.
+fn sanitize_backup_label(label: &str) -> Option<String> {
+    let label: String = label
+        .chars()
+        .filter(|c| c.is_alphanumeric() || " -_.()".contains(*c))
+        .take(MAX_BACKUP_LABEL_LEN)
+        .collect();
+    let label = label.trim_end_matches(['.', ' ']).trim_start();
+    if label.is_empty() {
+        None
+    } else {
+        Some(label.to_string())
+    }
 }
 
 // ─── Timestamp formatting ───────────────────────────────────────────
 
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub enum TimeFormat {
+    /// 2024-03-01 14:05:09
+    #[default]
+    Iso,
+    /// 03/01/2024 02:05 PM
+    Us,
+    /// "5 minutes ago"
+    Relative,
+}
+
+/// How every timestamp the backend returns is written, persisted in the settings store
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TimePreference {
+    pub format: TimeFormat,
+    /// Show every time in UTC (true) or the local timezone (false). Unset keeps the zone each
+    /// kind of timestamp has always been shown in, see `format_timestamp` and `format_system_time`.
+    pub utc: Option<bool>,
+}
+
+static TIME_PREFERENCE: Mutex<TimePreference> = Mutex::new(TimePreference {
+    format: TimeFormat::Iso,
+    utc: None,
+});
+
+/// Formats Unix seconds read from Steam's files (last login, last played), in UTC by default
 fn format_timestamp(secs: u64) -> String {
-    use chrono::{DateTime, Utc};
     if secs == 0 {
         return "Never".to_string();
     }
-    let dt = DateTime::<Utc>::from_timestamp(secs as i64, 0)
-        .unwrap_or_else(|| DateTime::<Utc>::from_timestamp(0, 0).unwrap());
-    dt.format("%Y-%m-%d %H:%M:%S").to_string()
+    format_time(SystemTime::UNIX_EPOCH + Duration::from_secs(secs), true)
+}
+
+/// Formats a file or clock time, in the local timezone by default
+fn format_system_time(time: SystemTime) -> String {
+    format_time(time, false)
+}
+
+/// Formats `time` with the user's `TimePreference`, falling back to `default_utc` for the zone
+fn format_time(time: SystemTime, default_utc: bool) -> String {
+    use chrono::{DateTime, Local, Utc};
+    let preference = *TIME_PREFERENCE.lock().unwrap();
+    let pattern = match preference.format {
+        TimeFormat::Iso => "%Y-%m-%d %H:%M:%S",
+        TimeFormat::Us => "%m/%d/%Y %I:%M %p",
+        TimeFormat::Relative => return format_relative_time(time),
+    };
+    if preference.utc.unwrap_or(default_utc) {
+        DateTime::<Utc>::from(time).format(pattern).to_string()
+    } else {
+        DateTime::<Local>::from(time).format(pattern).to_string()
+    }
+}
+
+fn format_relative_time(time: SystemTime) -> String {
+    // Times in the future (clock skew between machines) read as now
+    let secs = SystemTime::now()
+        .duration_since(time)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let (count, unit) = match secs {
+        0..60 => return "just now".to_string(),
+        60..3_600 => (secs / 60, "minute"),
+        3_600..86_400 => (secs / 3_600, "hour"),
+        86_400..2_592_000 => (secs / 86_400, "day"),
+        2_592_000..31_536_000 => (secs / 2_592_000, "month"),
+        _ => (secs / 31_536_000, "year"),
+    };
+    let plural = if count == 1 { "" } else { "s" };
+    format!("{} {}{} ago", count, unit, plural)
 }
 
 fn get_latest_modified_time(dir: &Path) -> u64 {
@@ -520,12 +1922,22 @@ fn get_latest_modified_time(dir: &Path) -> u64 {
 // ─── File stats ─────────────────────────────────────────────────────
 
 fn get_dir_stats(dir: &Path) -> (u64, usize, usize, Option<SystemTime>) {
+    get_dir_stats_with(dir, true)
+}
+
+/// `follow_links` controls whether symlinks and Windows junctions (how relocated userdata
+/// folders are usually set up) are walked into; link loops are skipped by WalkDir either way
+fn get_dir_stats_with(dir: &Path, follow_links: bool) -> (u64, usize, usize, Option<SystemTime>) {
     let mut total_size: u64 = 0;
     let mut file_count: usize = 0;
     let mut folder_count: usize = 0;
     let mut latest_modified: Option<SystemTime> = None;
 
-    for entry in WalkDir::new(dir).into_iter().flatten() {
+    for entry in WalkDir::new(dir)
+        .follow_links(follow_links)
+        .into_iter()
+        .flatten()
+    {
         let path = entry.path();
         if path.is_file() {
             file_count += 1;
@@ -552,53 +1964,343 @@ fn get_dir_stats(dir: &Path) -> (u64, usize, usize, Option<SystemTime>) {
     (total_size, file_count, folder_count, latest_modified)
 }
 
-fn format_system_time(time: SystemTime) -> String {
-    let datetime: chrono::DateTime<chrono::Local> = time.into();
-    datetime.format("%Y-%m-%d %H:%M:%S").to_string()
+/// Total file size only. Skips the folder counting and mtime tracking of `get_dir_stats` and
+/// reuses the metadata WalkDir already has, which matters on folders with many small files.
+fn dir_size_only(dir: &Path) -> u64 {
+    WalkDir::new(dir)
+        .follow_links(true)
+        .into_iter()
+        .flatten()
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| entry.metadata().ok())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+/// `1.5 GB` style, in binary units
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+/// Files under `dir` keyed by their relative path. Paths stay raw so names that aren't valid
+/// UTF-8 still resolve to the same file when joined back onto a folder.
+fn list_files_relative(dir: &Path) -> HashMap<PathBuf, fs::Metadata> {
+    WalkDir::new(dir)
+        .follow_links(true)
+        .into_iter()
+        .flatten()
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| {
+            let rel = entry.path().strip_prefix(dir).ok()?;
+            let metadata = entry.metadata().ok()?;
+            Some((rel.to_path_buf(), metadata))
+        })
+        .collect()
+}
+
+/// Same size and mtime is treated as unchanged; same size with a different mtime falls back
+/// to comparing contents
+fn files_differ(a: &Path, a_meta: &fs::Metadata, b: &Path, b_meta: &fs::Metadata) -> bool {
+    if a_meta.len() != b_meta.len() {
+        return true;
+    }
+    if a_meta.modified().ok() == b_meta.modified().ok() {
+        return false;
+    }
+    match (fs::read(a), fs::read(b)) {
+        (Ok(a_bytes), Ok(b_bytes)) => a_bytes != b_bytes,
+        _ => true,
+    }
+}
+
+/// Compares two game folders file by file, `source` being the side that would be copied over
+fn diff_folders(source: &Path, target: &Path) -> FileDiff {
+    let source_files = list_files_relative(source);
+    let target_files = list_files_relative(target);
+
+    let mut diff = FileDiff {
+        added: Vec::new(),
+        deleted: Vec::new(),
+        modified: Vec::new(),
+        unchanged_count: 0,
+        changed_bytes: 0,
+    };
+    for (rel, source_meta) in &source_files {
+        match target_files.get(rel) {
+            None => {
+                diff.added.push(normalize_path(rel));
+                diff.changed_bytes += source_meta.len();
+            }
+            Some(target_meta) => {
+                if files_differ(
+                    &source.join(rel),
+                    source_meta,
+                    &target.join(rel),
+                    target_meta,
+                ) {
+                    diff.modified.push(normalize_path(rel));
+                    diff.changed_bytes += source_meta.len();
+                } else {
+                    diff.unchanged_count += 1;
+                }
+            }
+        }
+    }
+    for (rel, target_meta) in &target_files {
+        if !source_files.contains_key(rel) {
+            diff.deleted.push(normalize_path(rel));
+            diff.changed_bytes += target_meta.len();
+        }
+    }
+
+    diff.added.sort();
+    diff.deleted.sort();
+    diff.modified.sort();
+    diff
+}
+
+// ─── Game name matching ─────────────────────────────────────────────
+
+fn matches_query(name: &str, query: &str) -> bool {
+    let name = name.to_lowercase();
+    let query = query.trim().to_lowercase();
+    if query.is_empty() || name.contains(&query) {
+        return true;
+    }
+
+    // Fuzzy fallback: every query character must appear in order, e.g. "bg3" → "Baldur's Gate 3"
+    let mut name_chars = name.chars();
+    query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .all(|qc| name_chars.any(|nc| nc == qc))
+}
+
+/// Shell-style glob (`*`, `?`) matched against the whole name, case-insensitively. Patterns
+/// without wildcards match as a plain substring.
+fn matches_pattern(name: &str, pattern: &str) -> bool {
+    let pattern = pattern.trim();
+    if !pattern.contains(['*', '?']) {
+        return name.to_lowercase().contains(&pattern.to_lowercase());
+    }
+
+    let mut re = String::from("(?i)^");
+    for c in pattern.chars() {
+        match c {
+            '*' => re.push_str(".*"),
+            '?' => re.push('.'),
+            _ => re.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    re.push('$');
+
+    regex::Regex::new(&re)
+        .map(|r| r.is_match(name))
+        .unwrap_or(false)
+}
+
+// ─── Swap estimate ──────────────────────────────────────────────────
+
+/// Copies smaller than this time little more than opening and flushing files, so their rate
+/// isn't kept for later estimates
+const BENCHMARK_BYTES: u64 = 8 * 1024 * 1024;
+const BENCHMARK_FILE_PREFIX: &str = ".nether-swap-bench";
+
+/// Copy throughput in bytes per second by destination drive (`drive_label`), from the last
+/// benchmark or swap that wrote at least `BENCHMARK_BYTES` there, so repeated estimates stay
+/// instant
+static MEASURED_THROUGHPUT: Mutex<Vec<(String, f64)>> = Mutex::new(Vec::new());
+
+fn record_copy_throughput(drive: &str, bytes: u64, elapsed: Duration) {
+    let secs = elapsed.as_secs_f64();
+    if bytes < BENCHMARK_BYTES || secs <= 0.0 {
+        return;
+    }
+    let rate = bytes as f64 / secs;
+    let mut measured = MEASURED_THROUGHPUT.lock().unwrap();
+    match measured.iter_mut().find(|(d, _)| d == drive) {
+        Some((_, known)) => *known = rate,
+        None => measured.push((drive.to_string(), rate)),
+    }
+}
+
+/// Copy throughput into `drive` in bytes per second. Without an earlier measurement, times
+/// copying up to `BENCHMARK_BYTES` of `sample` (files the swap will copy) into a scratch file
+/// in `scratch_dir` on that drive, flushed to disk so the page cache doesn't hide the write.
+fn measure_copy_throughput(drive: &str, sample: &[PathBuf], scratch_dir: &Path) -> Option<f64> {
+    let known = MEASURED_THROUGHPUT
+        .lock()
+        .unwrap()
+        .iter()
+        .find(|(d, _)| d == drive)
+        .map(|(_, rate)| *rate);
+    if known.is_some() {
+        return known;
+    }
+
+    let scratch = scratch_dir.join(format!("{}.tmp", BENCHMARK_FILE_PREFIX));
+    let started = Instant::now();
+    let copied = copy_benchmark_sample(sample, &scratch);
+    let elapsed = started.elapsed();
+    let _ = fs::remove_file(&scratch);
+
+    let copied = copied.ok()?;
+    let secs = elapsed.as_secs_f64();
+    if copied == 0 || secs <= 0.0 {
+        return None;
+    }
+    record_copy_throughput(drive, copied, elapsed);
+    Some(copied as f64 / secs)
+}
+
+fn copy_benchmark_sample(sample: &[PathBuf], scratch: &Path) -> io::Result<u64> {
+    use io::Read;
+    let mut out = fs::File::create(scratch)?;
+    let mut copied = 0;
+    for file in sample {
+        let remaining = BENCHMARK_BYTES - copied;
+        if remaining == 0 {
+            break;
+        }
+        copied += io::copy(&mut fs::File::open(file)?.take(remaining), &mut out)?;
+    }
+    out.sync_all()?;
+    Ok(copied)
+}
+
+/// The first files of the source's games adding up to `BENCHMARK_BYTES`
+fn benchmark_sample(source_base: &Path, game_ids: &[String]) -> Vec<PathBuf> {
+    let mut sample = Vec::new();
+    let mut bytes = 0;
+    let files = game_ids
+        .iter()
+        .flat_map(|gid| WalkDir::new(source_base.join(gid)))
+        .flatten()
+        .filter(|e| e.file_type().is_file());
+    for entry in files {
+        if bytes >= BENCHMARK_BYTES {
+            break;
+        }
+        bytes += entry.metadata().map(|m| m.len()).unwrap_or(0);
+        sample.push(entry.into_path());
+    }
+    sample
+}
+
+// ─── Swap stats ─────────────────────────────────────────────────────
+
+/// Runs kept in the store, oldest dropped first
+const MAX_SWAP_STATS: usize = 20;
+
+fn read_swap_stats(app: &AppHandle) -> Vec<SwapStats> {
+    app.store(SETTINGS_STORE)
+        .ok()
+        .and_then(|store| store.get(SWAP_STATS_KEY))
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default()
+}
+
+/// Stores the run's throughput. Estimates follow what swaps actually achieve through
+/// `record_copy_throughput`, which `swap_into_targets` feeds per drive.
+fn record_swap_stats(app: &AppHandle, result: &SwapResult) -> Result<(), String> {
+    if result.bytes_copied == 0 || result.elapsed_ms == 0 {
+        return Ok(());
+    }
+
+    // The run's time includes taking backups, so their bytes count towards the rate too
+    let bytes_written = result.bytes_written.max(result.bytes_copied);
+    let rate = bytes_written as f64 / (result.elapsed_ms as f64 / 1000.0);
+
+    let mut history = read_swap_stats(app);
+    history.push(SwapStats {
+        timestamp: SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0),
+        bytes_copied: result.bytes_copied,
+        files_copied: result.files_copied,
+        elapsed_ms: result.elapsed_ms,
+        mb_per_sec: rate / (1024.0 * 1024.0),
+    });
+    let excess = history.len().saturating_sub(MAX_SWAP_STATS);
+    history.drain(..excess);
+
+    let store = app.store(SETTINGS_STORE).map_err(|e| e.to_string())?;
+    let value = serde_json::to_value(&history).map_err(|e| e.to_string())?;
+    store.set(SWAP_STATS_KEY, value);
+    store.save().map_err(|e| e.to_string())
 }
 
+// ─── Userdata watcher ───────────────────────────────────────────────
+
+/// Changes are batched for this long, so a swap writing thousands of files only produces an
+/// event every couple of seconds instead of one per file
+const WATCH_DEBOUNCE: Duration = Duration::from_secs(2);
+
+static USERDATA_WATCHER: Mutex<Option<Debouncer<RecommendedWatcher>>> = Mutex::new(None);
+
 // ─── Tauri commands ─────────────────────────────────────────────────
 
 #[tauri::command]
 fn detect_steam() -> Result<AppState, String> {
-    let steam_path = detect_steam_path().ok_or("Could not detect Steam installation")?;
+    let steam_path = detect_steam_path().ok_or_else(|| {
+        log::warn!("Could not detect a Steam installation");
+        "Could not detect Steam installation"
+    })?;
+    log::info!("Detected Steam at {}", steam_path.display());
     let userdata_path = find_userdata_path(&steam_path)
         .ok_or("Could not find userdata folder in Steam directory")?;
 
     Ok(AppState {
         userdata_path: normalize_path(&userdata_path),
         steam_path: normalize_path(&steam_path),
+        backups_dir_name: backups_dir_name(),
     })
 }
 
+fn looks_like_userdata(p: &Path) -> bool {
+    p.is_dir()
+        && fs::read_dir(p)
+            .map(|entries| {
+                entries.flatten().any(|e| {
+                    let path = e.path();
+                    path.is_dir() && numeric_folder_name(&path).is_some()
+                })
+            })
+            .unwrap_or(false)
+}
+
 #[tauri::command]
-fn validate_steam_path(path: String) -> Result<AppState, String> {
+fn validate_steam_path(path: String, userdata_path: Option<String>) -> Result<AppState, String> {
     let p = PathBuf::from(&path);
     if !p.exists() {
         return Err("Path does not exist".to_string());
     }
 
-    // Check if user gave us the userdata folder directly
-    if p.file_name().map(|n| n == "userdata").unwrap_or(false) && p.is_dir() {
-        let has_numeric = fs::read_dir(&p)
-            .map(|entries| {
-                entries.flatten().any(|e| {
-                    e.path().is_dir()
-                        && e.file_name()
-                            .to_string_lossy()
-                            .chars()
-                            .all(|c| c.is_ascii_digit())
-                })
-            })
-            .unwrap_or(false);
+    // A separately chosen userdata folder (relocated or portable installs) wins over detection
+    if let Some(ud) = userdata_path {
+        return set_userdata_path(path, ud);
+    }
 
-        if has_numeric {
-            let steam_path = p.parent().unwrap_or(&p);
-            return Ok(AppState {
-                userdata_path: normalize_path(&p),
-                steam_path: normalize_path(steam_path),
-            });
-        }
+    // Check if user gave us the userdata folder directly
+    if p.file_name().map(|n| n == "userdata").unwrap_or(false) && looks_like_userdata(&p) {
+        let steam_path = p.parent().unwrap_or(&p);
+        return Ok(AppState {
+            userdata_path: normalize_path(&p),
+            steam_path: normalize_path(steam_path),
+            backups_dir_name: backups_dir_name(),
+        });
     }
 
     // Check if it's a Steam folder with userdata inside
@@ -606,67 +2308,621 @@ fn validate_steam_path(path: String) -> Result<AppState, String> {
         return Ok(AppState {
             userdata_path: normalize_path(&ud),
             steam_path: normalize_path(&p),
+            backups_dir_name: backups_dir_name(),
         });
     }
 
+    // A game install, steamapps or library folder was pasted: walk up to the Steam root, the
+    // folder that has both steamapps and userdata
+    let mut library_dirs = Vec::new();
+    for dir in p.ancestors().skip(1) {
+        if !dir.join("steamapps").is_dir() {
+            continue;
+        }
+        if let Some(ud) = find_userdata_path(dir) {
+            return Ok(AppState {
+                userdata_path: normalize_path(&ud),
+                steam_path: normalize_path(dir),
+                backups_dir_name: backups_dir_name(),
+            });
+        }
+        library_dirs.push(normalize_path(dir));
+    }
+    if p.join("steamapps").is_dir() {
+        library_dirs.insert(0, normalize_path(&p));
+    }
+
+    if !library_dirs.is_empty() {
+        return Err(format!(
+            "Found a Steam library at {} but no 'userdata' folder next to it. Additional libraries only hold games; please select the main Steam folder instead.",
+            library_dirs.join(", ")
+        ));
+    }
+
     Err("Could not find 'userdata' folder. Please select the Steam folder or the userdata folder directly.".to_string())
 }
 
 #[tauri::command]
-fn get_profiles(userdata_path: String, steam_path: String) -> Vec<Profile> {
+fn set_userdata_path(steam_path: String, userdata_path: String) -> Result<AppState, String> {
+    let steam = PathBuf::from(&steam_path);
+    if !steam.is_dir() {
+        return Err("Steam path does not exist".to_string());
+    }
+
+    // userdata may live anywhere (another drive, a symlink target), it need not be under steam_path
+    let ud = PathBuf::from(&userdata_path);
+    if !ud.is_dir() {
+        return Err("Userdata path does not exist".to_string());
+    }
+    if !looks_like_userdata(&ud) {
+        return Err("The selected folder does not contain any Steam account folders".to_string());
+    }
+
+    Ok(AppState {
+        userdata_path: normalize_path(&ud),
+        steam_path: normalize_path(&steam),
+        backups_dir_name: backups_dir_name(),
+    })
+}
+
+#[tauri::command]
+fn get_profiles(
+    userdata_path: String,
+    steam_path: String,
+    sort: Option<ProfileSort>,
+    min_games: Option<usize>,
+    include_offline_account: Option<bool>,
+    include_sizes: Option<bool>,
+) -> Vec<Profile> {
+    let ud = Path::new(&userdata_path);
     let steam = Path::new(&steam_path);
     let steamapps_dirs = find_all_steamapps_dirs(steam);
-    discover_profiles(Path::new(&userdata_path), steam, &steamapps_dirs)
+    let mut profiles = discover_profiles(ud, steam, &steamapps_dirs);
+    if include_sizes.unwrap_or(false) {
+        fill_profile_sizes(ud, &mut profiles);
+    }
+    // Unreadable profiles stay listed, their game count of 0 says nothing
+    let min_games = min_games.unwrap_or(0);
+    profiles.retain(|p| p.is_backup || p.read_error.is_some() || p.game_count >= min_games);
+    if !include_offline_account.unwrap_or(true) {
+        profiles.retain(|p| !p.is_offline_account);
+    }
+    if let Some(sort) = sort {
+        sort_profiles(&mut profiles, sort);
+    }
+    profiles
+}
+
+/// Sums every profile's game folders, a profile at a time on one worker per CPU
+fn fill_profile_sizes(ud: &Path, profiles: &mut [Profile]) {
+    let profile_paths: Vec<PathBuf> = profiles
+        .iter()
+        .map(|p| profile_base_path(ud, &p.id, p.is_backup))
+        .collect();
+    let workers = std::thread::available_parallelism()
+        .map_or(1, |n| n.get())
+        .min(profile_paths.len());
+    let next = AtomicUsize::new(0);
+    let sizes: Vec<(usize, u64)> = std::thread::scope(|scope| {
+        let workers: Vec<_> = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = Vec::new();
+                    loop {
+                        let i = next.fetch_add(1, Ordering::AcqRel);
+                        let Some(profile_path) = profile_paths.get(i) else {
+                            break;
+                        };
+                        let size = list_game_folder_ids(profile_path)
+                            .iter()
+                            .map(|game_id| dir_size_only(&profile_path.join(game_id)))
+                            .sum();
+                        done.push((i, size));
+                    }
+                    done
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|w| w.join().unwrap_or_else(|e| std::panic::resume_unwind(e)))
+            .collect()
+    });
+
+    for (i, size) in sizes {
+        profiles[i].total_size_bytes = size;
+        profiles[i].total_size = format_size(size);
+    }
 }
 
+/// Profiles and backups holding save data for `game_id`, sorted by last login, to pre-fill a
+/// swap between them
 #[tauri::command]
-fn get_games_for_profile(
-    steam_path: String,
+fn profiles_with_game(
     userdata_path: String,
-    profile_id: String,
-    is_backup: bool,
-) -> Vec<GameInfo> {
-    let ud = PathBuf::from(&userdata_path);
+    steam_path: String,
+    game_id: String,
+) -> Result<Vec<Profile>, String> {
+    sanitize_id(&game_id)?;
+    let ud = Path::new(&userdata_path);
     let steam = Path::new(&steam_path);
     let steamapps_dirs = find_all_steamapps_dirs(steam);
-    let appinfo_games = get_appinfo_games(steam);
+    let mut profiles = discover_profiles(ud, steam, &steamapps_dirs);
+    profiles.retain(|p| {
+        has_meaningful_game_data(&profile_base_path(ud, &p.id, p.is_backup).join(&game_id))
+    });
+    sort_profiles(&mut profiles, ProfileSort::LastLogin);
+    Ok(profiles)
+}
 
-    let profile_path = if is_backup {
-        ud.join("dunabackups").join(&profile_id)
-    } else {
-        ud.join(&profile_id)
-    };
+#[tauri::command]
+fn profile_details(userdata_path: String, profile_id: String) -> Result<ProfileDetails, String> {
+    sanitize_id(&profile_id)?;
+    let profile_path = PathBuf::from(&userdata_path).join(&profile_id);
+    if !profile_path.is_dir() {
+        return Err("Profile not found".to_string());
+    }
 
-    let mut games = Vec::new();
-    if let Ok(entries) = fs::read_dir(&profile_path) {
-        for entry in entries.flatten() {
-            let path = entry.path();
-            if !path.is_dir() {
-                continue;
-            }
-            let folder_name = match path.file_name() {
-                Some(n) => n.to_string_lossy().to_string(),
-                None => continue,
-            };
-            if !folder_name.chars().all(|c| c.is_ascii_digit()) {
-                continue;
+    let mut persona_name = None;
+    let mut friends = std::collections::HashSet::new();
+    if let Ok(content) = fs::read_to_string(profile_path.join("config").join("localconfig.vdf")) {
+        for_each_vdf_value(&content, |sections, key, value| {
+            if persona_name.is_none() && key.eq_ignore_ascii_case("PersonaName") {
+                persona_name = Some(value.to_string());
             }
-            if !has_meaningful_game_data(&path) {
-                continue;
+            // friends/<account id>/{name, avatar, ...}
+            let is_friend = sections.len() == 3
+                && sections[1].eq_ignore_ascii_case("friends")
+                && sections[2].chars().all(|c| c.is_ascii_digit());
+            if is_friend {
+                friends.insert(sections[2].clone());
             }
-            if let Some((name, _)) = get_game_info(&appinfo_games, &steamapps_dirs, &folder_name) {
-                games.push(GameInfo {
-                    id: folder_name,
-                    name,
-                });
-            }
-        }
+        });
     }
 
-    games.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+    let last_played = get_last_played_times(&profile_path)
+        .into_iter()
+        .max_by_key(|(_, secs)| *secs);
+
+    Ok(ProfileDetails {
+        steamid64: to_steamid64(&profile_id).map(|id| id.to_string()),
+        id: profile_id,
+        persona_name,
+        friend_count: friends.len(),
+        last_played: format_timestamp(last_played.as_ref().map(|(_, secs)| *secs).unwrap_or(0)),
+        last_played_game_id: last_played.map(|(game_id, _)| game_id),
+    })
+}
+
+#[tauri::command]
+fn current_user(steam_path: String) -> Option<CurrentUser> {
+    find_current_user(Path::new(&steam_path))
+}
+
+/// Every account in loginusers.vdf, matched against the userdata folders so the UI can tell
+/// accounts with saves from known but empty ones. `userdata_path` defaults to the Steam
+/// installation's userdata folder.
+#[tauri::command]
+fn all_known_users(steam_path: String, userdata_path: Option<String>) -> Vec<KnownUser> {
+    let steam = Path::new(&steam_path);
+    let ud = userdata_path
+        .map(PathBuf::from)
+        .unwrap_or_else(|| steam.join("userdata"));
+    let steamapps_dirs = find_all_steamapps_dirs(steam);
+    let profiles: HashMap<String, Profile> = discover_profiles(&ud, steam, &steamapps_dirs)
+        .into_iter()
+        .filter(|p| !p.is_backup)
+        .map(|p| (p.id.clone(), p))
+        .collect();
+
+    let mut users: Vec<KnownUser> = read_login_users(steam)
+        .into_iter()
+        .filter_map(|(steamid64, user)| {
+            let id = to_account_id(&steamid64)?.to_string();
+            let profile = profiles.get(&id);
+            Some(KnownUser {
+                has_userdata: ud.join(&id).is_dir(),
+                game_count: profile.map(|p| p.game_count).unwrap_or(0),
+                id,
+                steamid64,
+                persona_name: user.persona_name,
+                most_recent: user.most_recent,
+            })
+        })
+        .collect();
+    users.sort_by(|a, b| {
+        b.most_recent
+            .cmp(&a.most_recent)
+            .then_with(|| a.id.cmp(&b.id))
+    });
+    users
+}
+
+/// Builds a single profile without discovering all the others
+#[tauri::command]
+fn get_profile(
+    userdata_path: String,
+    steam_path: String,
+    profile_id: String,
+    is_backup: bool,
+) -> Option<Profile> {
+    sanitize_profile_id(&profile_id, is_backup).ok()?;
+    let ud = PathBuf::from(&userdata_path);
+    let steam = Path::new(&steam_path);
+    let steamapps_dirs = find_all_steamapps_dirs(steam);
+    let appinfo_games = get_appinfo_games(steam);
+
+    let path = profile_base_path(&ud, &profile_id, is_backup);
+    if !path.is_dir() {
+        return None;
+    }
+    let drives = Drives::list();
+    if is_backup {
+        let snapshot = BackupSnapshot::from_path(&path)?;
+        discover_backup_profile(
+            &snapshot,
+            &ud,
+            steam,
+            &appinfo_games,
+            &steamapps_dirs,
+            &drives,
+        )
+    } else {
+        discover_regular_profile(&path, &ud, steam, &appinfo_games, &steamapps_dirs, &drives)
+    }
+}
+
+#[tauri::command]
+async fn overview(userdata_path: String, steam_path: String) -> Overview {
+    let ud = PathBuf::from(&userdata_path);
+    let steam = Path::new(&steam_path);
+    let steamapps_dirs = find_all_steamapps_dirs(steam);
+    let profiles = discover_profiles(&ud, steam, &steamapps_dirs);
+
+    let regular: Vec<&Profile> = profiles.iter().filter(|p| !p.is_backup).collect();
+
+    // Walking every save folder is the slow part, so size each profile on its own thread
+    let total_save_bytes = std::thread::scope(|scope| {
+        let handles: Vec<_> = regular
+            .iter()
+            .map(|profile| {
+                let profile_path = ud.join(&profile.id);
+                scope.spawn(move || {
+                    list_game_folder_ids(&profile_path)
+                        .iter()
+                        .map(|game_id| dir_size_only(&profile_path.join(game_id)))
+                        .sum::<u64>()
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|h| h.join().unwrap_or(0))
+            .sum::<u64>()
+    });
+
+    Overview {
+        profile_count: regular.len(),
+        backup_count: profiles.len() - regular.len(),
+        total_games: regular.iter().map(|p| p.game_count).sum(),
+        total_save_bytes,
+    }
+}
+
+#[tauri::command]
+fn get_games_for_profile(
+    steam_path: String,
+    userdata_path: String,
+    profile_id: String,
+    is_backup: bool,
+    query: Option<String>,
+) -> Vec<GameInfo> {
+    if sanitize_profile_id(&profile_id, is_backup).is_err() {
+        return vec![];
+    }
+    let ud = PathBuf::from(&userdata_path);
+    let steam = Path::new(&steam_path);
+    let steamapps_dirs = find_all_steamapps_dirs(steam);
+    let appinfo_games = get_appinfo_games(steam);
+
+    let profile_path = profile_base_path(&ud, &profile_id, is_backup);
+
+    let mut games = list_profile_games(&profile_path, &appinfo_games, &steamapps_dirs);
+    if let Some(ref q) = query {
+        games.retain(|g| matches_query(&g.name, q));
+    }
     games
 }
 
+/// Games the profile has saves for that Steam knows (appinfo) but that aren't installed in any
+/// library, i.e. saves worth backing up before the profile is reset
+#[tauri::command]
+fn uninstalled_with_saves(
+    userdata_path: String,
+    steam_path: String,
+    profile_id: String,
+) -> Result<Vec<GameInfo>, String> {
+    sanitize_id(&profile_id)?;
+    let steam = Path::new(&steam_path);
+    let steamapps_dirs = find_all_steamapps_dirs(steam);
+    let appinfo_games = get_appinfo_games(steam);
+
+    let mut games = list_profile_games(
+        &Path::new(&userdata_path).join(&profile_id),
+        &appinfo_games,
+        &steamapps_dirs,
+    );
+    games.retain(|g| {
+        let manifest_name = format!("appmanifest_{}.acf", g.id);
+        appinfo_games.contains_key(&g.id)
+            && !steamapps_dirs
+                .iter()
+                .any(|dir| dir.join(&manifest_name).is_file())
+    });
+    Ok(games)
+}
+
+/// Lists the games in the backup snapshot `backup_id`. Unlike `get_games_for_profile` with
+/// `is_backup`, this always reads the backup folder.
+#[tauri::command]
+fn get_games_for_backup(
+    userdata_path: String,
+    steam_path: String,
+    backup_id: String,
+) -> Result<Vec<GameInfo>, String> {
+    sanitize_backup_id(&backup_id)?;
+    let backup_path = profile_base_path(Path::new(&userdata_path), &backup_id, true);
+    if !backup_path.is_dir() {
+        return Err("Backup not found".to_string());
+    }
+
+    let steam = Path::new(&steam_path);
+    let steamapps_dirs = find_all_steamapps_dirs(steam);
+    let appinfo_games = get_appinfo_games(steam);
+
+    Ok(list_profile_games(
+        &backup_path,
+        &appinfo_games,
+        &steamapps_dirs,
+    ))
+}
+
+#[tauri::command]
+fn resolve_games_by_pattern(
+    userdata_path: String,
+    steam_path: String,
+    profile_id: String,
+    is_backup: bool,
+    pattern: String,
+) -> Result<Vec<GameInfo>, String> {
+    sanitize_profile_id(&profile_id, is_backup)?;
+    let ud = PathBuf::from(&userdata_path);
+    let steam = Path::new(&steam_path);
+    let steamapps_dirs = find_all_steamapps_dirs(steam);
+    let appinfo_games = get_appinfo_games(steam);
+
+    let profile_path = profile_base_path(&ud, &profile_id, is_backup);
+
+    let mut games = list_profile_games(&profile_path, &appinfo_games, &steamapps_dirs);
+    games.retain(|g| matches_pattern(&g.name, &pattern));
+    Ok(games)
+}
+
+#[tauri::command]
+fn diff_game(
+    userdata_path: String,
+    source_id: String,
+    source_is_backup: bool,
+    target_id: String,
+    game_id: String,
+) -> Result<FileDiff, String> {
+    sanitize_profile_id(&source_id, source_is_backup)?;
+    sanitize_ids([&target_id, &game_id])?;
+    let ud = PathBuf::from(&userdata_path);
+    let source_game = profile_base_path(&ud, &source_id, source_is_backup).join(&game_id);
+    let target_game = ud.join(&target_id).join(&game_id);
+    if !source_game.is_dir() {
+        return Err("Source game data not found".to_string());
+    }
+
+    Ok(diff_folders(&source_game, &target_game))
+}
+
+/// A blake3 hash over the sorted relative paths, sizes and content hashes of a game's files,
+/// so identical saves get the same fingerprint whichever profile holds them. remotecache.vdf is
+/// left out since it is Steam Cloud state, not save data.
+#[tauri::command]
+fn game_fingerprint(
+    userdata_path: String,
+    profile_id: String,
+    is_backup: bool,
+    game_id: String,
+) -> Result<String, String> {
+    sanitize_profile_id(&profile_id, is_backup)?;
+    sanitize_id(&game_id)?;
+    let game_path =
+        profile_base_path(Path::new(&userdata_path), &profile_id, is_backup).join(&game_id);
+    if !game_path.is_dir() {
+        return Err("Game data not found".to_string());
+    }
+    fingerprint_folder(&game_path)
+}
+
+fn fingerprint_folder(game_path: &Path) -> Result<String, String> {
+    let mut files: Vec<(String, PathBuf, u64)> = list_files_relative(game_path)
+        .into_iter()
+        .filter(|(rel, _)| !is_remotecache(&game_path.join(rel)))
+        .map(|(rel, metadata)| (normalize_path(&rel), rel, metadata.len()))
+        .collect();
+    files.sort();
+
+    let mut hasher = blake3::Hasher::new();
+    for (name, rel, size) in files {
+        let mut file_hasher = blake3::Hasher::new();
+        let mut file = fs::File::open(game_path.join(&rel))
+            .map_err(|e| format!("Failed to read {}: {}", name, e))?;
+        io::copy(&mut file, &mut file_hasher)
+            .map_err(|e| format!("Failed to read {}: {}", name, e))?;
+
+        // Length-prefixed so path boundaries can't shift between entries
+        hasher.update(&(name.len() as u64).to_le_bytes());
+        hasher.update(name.as_bytes());
+        hasher.update(&size.to_le_bytes());
+        hasher.update(file_hasher.finalize().as_bytes());
+    }
+    Ok(hasher.finalize().to_hex().to_string())
+}
+
+/// Checks after a swap that every target holds the same files as the source, game by game.
+/// remotecache.vdf is left out of the comparison like in `game_fingerprint`.
+#[tauri::command]
+fn confirm_swap(
+    userdata_path: String,
+    source_id: String,
+    source_is_backup: bool,
+    target_ids: Vec<String>,
+    game_ids: Vec<String>,
+) -> Result<Vec<ConfirmResult>, String> {
+    sanitize_profile_id(&source_id, source_is_backup)?;
+    sanitize_ids(&target_ids)?;
+    sanitize_game_ids(&game_ids)?;
+    let ud = PathBuf::from(&userdata_path);
+    let source_base = profile_base_path(&ud, &source_id, source_is_backup);
+
+    let mut results = Vec::new();
+    for game_id in resolve_game_selection(&source_base, &game_ids, &[]) {
+        let source_game = source_base.join(&game_id);
+        let source_fingerprint = source_game
+            .is_dir()
+            .then(|| fingerprint_folder(&source_game));
+        for target_id in &target_ids {
+            let target_game = ud.join(target_id).join(&game_id);
+            let status = match &source_fingerprint {
+                None => ConfirmStatus::Skipped,
+                Some(Err(e)) => ConfirmStatus::Failed(format!("Source: {}", e)),
+                Some(Ok(_)) if !target_game.is_dir() => ConfirmStatus::Missing,
+                Some(Ok(expected)) => match fingerprint_folder(&target_game) {
+                    Ok(actual) if &actual == expected => ConfirmStatus::Match,
+                    Ok(_) => ConfirmStatus::Mismatch,
+                    Err(e) => ConfirmStatus::Failed(e),
+                },
+            };
+            results.push(ConfirmResult {
+                target_id: target_id.clone(),
+                game_id: game_id.clone(),
+                status,
+            });
+        }
+    }
+    Ok(results)
+}
+
+/// The `top_n` biggest files of a game's save folder as (relative path, size), largest first
+#[tauri::command]
+fn largest_files(
+    userdata_path: String,
+    profile_id: String,
+    is_backup: bool,
+    game_id: String,
+    top_n: usize,
+) -> Result<Vec<(String, u64)>, String> {
+    sanitize_profile_id(&profile_id, is_backup)?;
+    sanitize_id(&game_id)?;
+    let game_path =
+        profile_base_path(Path::new(&userdata_path), &profile_id, is_backup).join(&game_id);
+    if !game_path.is_dir() {
+        return Err("Game data not found".to_string());
+    }
+
+    let mut files: Vec<(String, u64)> = list_files_relative(&game_path)
+        .into_iter()
+        .map(|(rel, metadata)| (normalize_path(&rel), metadata.len()))
+        .collect();
+    files.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    files.truncate(top_n);
+    Ok(files)
+}
+
+#[tauri::command]
+fn backup_drift(
+    userdata_path: String,
+    profile_id: String,
+    game_id: String,
+) -> Result<DriftReport, String> {
+    sanitize_ids([&profile_id, &game_id])?;
+    let ud = PathBuf::from(&userdata_path);
+    let live_game = ud.join(&profile_id).join(&game_id);
+    if !live_game.is_dir() {
+        return Err("Game data not found".to_string());
+    }
+    // Compared against the newest backup, the one a restore would most likely use
+    let Some(snapshot) = latest_game_backup(&ud, &profile_id, &game_id) else {
+        return Err("No backup of this game yet".to_string());
+    };
+
+    let diff = diff_folders(&live_game, &snapshot.path.join(&game_id));
+    let backup_created = read_backup_manifest(&snapshot.path)
+        .games
+        .get(&game_id)
+        .map_or(snapshot.created, |entry| entry.created);
+    let backup_created = format_timestamp(backup_created);
+
+    Ok(DriftReport {
+        added: diff.added.len(),
+        modified: diff.modified.len(),
+        removed: diff.deleted.len(),
+        changed_bytes: diff.changed_bytes,
+        backup_created,
+    })
+}
+
+#[tauri::command]
+fn compare_profiles(
+    userdata_path: String,
+    steam_path: String,
+    profile_a: String,
+    a_is_backup: bool,
+    profile_b: String,
+    b_is_backup: bool,
+) -> Result<ProfileDiff, String> {
+    sanitize_profile_id(&profile_a, a_is_backup)?;
+    sanitize_profile_id(&profile_b, b_is_backup)?;
+    let ud = PathBuf::from(&userdata_path);
+    let steam = Path::new(&steam_path);
+    let steamapps_dirs = find_all_steamapps_dirs(steam);
+    let appinfo_games = get_appinfo_games(steam);
+
+    let games_a = list_profile_games(
+        &profile_base_path(&ud, &profile_a, a_is_backup),
+        &appinfo_games,
+        &steamapps_dirs,
+    );
+    let games_b = list_profile_games(
+        &profile_base_path(&ud, &profile_b, b_is_backup),
+        &appinfo_games,
+        &steamapps_dirs,
+    );
+
+    let mut diff = ProfileDiff {
+        only_in_a: vec![],
+        only_in_b: vec![],
+        in_both: vec![],
+    };
+    for game in &games_a {
+        if games_b.iter().any(|g| g.id == game.id) {
+            diff.in_both.push(game.clone());
+        } else {
+            diff.only_in_a.push(game.clone());
+        }
+    }
+    diff.only_in_b = games_b
+        .into_iter()
+        .filter(|g| !games_a.iter().any(|a| a.id == g.id))
+        .collect();
+
+    Ok(diff)
+}
+
 #[tauri::command]
 fn get_swap_summary(
     userdata_path: String,
@@ -675,8 +2931,14 @@ fn get_swap_summary(
     source_is_backup: bool,
     target_ids: Vec<String>,
     game_ids: Vec<String>,
+    options: Option<SwapOptions>,
 ) -> Result<SwapSummary, String> {
     let ud = PathBuf::from(&userdata_path);
+    let options = options.unwrap_or_default();
+    sanitize_profile_id(&source_id, source_is_backup)?;
+    validate_swap_selection(&target_ids, &game_ids, &options)?;
+    reject_self_target(&source_id, source_is_backup, &target_ids, &options)?;
+    reject_unknown_targets(&ud, &target_ids, &options)?;
     let steam = Path::new(&steam_path);
     let steamapps_dirs = find_all_steamapps_dirs(steam);
     let profiles = discover_profiles(&ud, steam, &steamapps_dirs);
@@ -689,7 +2951,13 @@ fn get_swap_summary(
 
     let targets: Vec<Profile> = profiles
         .iter()
-        .filter(|p| target_ids.contains(&p.id) && !p.is_backup)
+        .filter(|p| {
+            if p.is_backup {
+                options.backup_target_ids.contains(&p.id)
+            } else {
+                target_ids.contains(&p.id)
+            }
+        })
         .cloned()
         .collect();
 
@@ -697,25 +2965,37 @@ fn get_swap_summary(
         return Err("No valid target profiles found".to_string());
     }
 
+    let source_base = profile_base_path(&ud, &source.id, source.is_backup);
+    let game_ids = resolve_game_selection(&source_base, &game_ids, &options.exclude_game_ids);
+
     if game_ids.is_empty() {
         return Err("No games selected".to_string());
     }
 
-    let source_base = if source.is_backup {
-        ud.join("dunabackups").join(&source.id)
-    } else {
-        ud.join(&source.id)
-    };
-
     let mut total_size: u64 = 0;
     let mut file_count: usize = 0;
     let mut folder_count: usize = 0;
     let mut latest_modified: Option<SystemTime> = None;
+    let mut per_game = Vec::new();
+    let appinfo_games = get_appinfo_games(steam);
 
     for game_id in &game_ids {
         let game_path = source_base.join(game_id);
         if game_path.exists() {
-            let (size, files, folders, modified) = get_dir_stats(&game_path);
+            let (size, files, folders, modified) =
+                get_dir_stats_with(&game_path, !options.skip_reparse_points);
+            per_game.push(GameStats {
+                game_id: game_id.clone(),
+                game_name: get_game_info(&appinfo_games, &steamapps_dirs, game_id)
+                    .map(|(name, _)| name)
+                    .unwrap_or_else(|| game_id.clone()),
+                size,
+                file_count: files,
+                folder_count: folders,
+                last_modified: modified
+                    .map(format_system_time)
+                    .unwrap_or_else(|| "Unknown".to_string()),
+            });
             total_size += size;
             file_count += files;
             folder_count += folders;
@@ -740,193 +3020,3861 @@ fn get_swap_summary(
         source_total_size: total_size,
         source_file_count: file_count,
         source_folder_count: folder_count,
+        effective_game_ids: game_ids,
+        steam_running: is_steam_running(),
+        per_game,
+    })
+}
+
+/// The swap summary's source size times each target, at the throughput of the target's drive
+#[tauri::command]
+fn estimate_swap(
+    userdata_path: String,
+    steam_path: String,
+    source_id: String,
+    source_is_backup: bool,
+    target_ids: Vec<String>,
+    game_ids: Vec<String>,
+    options: Option<SwapOptions>,
+) -> Result<EstimateReport, String> {
+    let summary = get_swap_summary(
+        userdata_path.clone(),
+        steam_path,
+        source_id,
+        source_is_backup,
+        target_ids,
+        game_ids,
+        options,
+    )?;
+    let source_size = summary.source_total_size;
+    let total_bytes = source_size * summary.targets.len() as u64;
+
+    let sample = benchmark_sample(Path::new(&summary.source.path), &summary.effective_game_ids);
+    // The scratch file goes into the backups folder, unless that is on another drive
+    let backups_dir = backups_dir(Path::new(&userdata_path));
+    let backups_drive = drive_label(&backups_dir, &Drives::list());
+    let seconds: Option<f64> = summary
+        .targets
+        .iter()
+        .map(|target| {
+            let scratch_dir = if backups_dir.is_dir() && backups_drive == target.drive {
+                backups_dir.as_path()
+            } else {
+                Path::new(&target.path)
+            };
+            measure_copy_throughput(&target.drive, &sample, scratch_dir)
+                .map(|rate| source_size as f64 / rate)
+        })
+        .sum();
+
+    let throughput = seconds
+        .filter(|&secs| secs > 0.0)
+        .map(|secs| total_bytes as f64 / secs);
+    Ok(EstimateReport {
+        total_bytes,
+        estimated_seconds: seconds.map(|secs| secs.ceil() as u64),
+        throughput_mb_per_sec: throughput.map(|rate| rate / (1024.0 * 1024.0)),
+    })
+}
+
+/// The source files a swap would copy, per game and sorted by path, at most `limit` of them
+#[tauri::command]
+fn swap_file_list(
+    userdata_path: String,
+    source_id: String,
+    source_is_backup: bool,
+    game_ids: Vec<String>,
+    limit: usize,
+    options: Option<SwapOptions>,
+) -> Result<SwapFileList, String> {
+    let ud = PathBuf::from(&userdata_path);
+    let options = options.unwrap_or_default();
+    sanitize_profile_id(&source_id, source_is_backup)?;
+    validate_swap_selection(&[], &game_ids, &options)?;
+
+    let source_base = profile_base_path(&ud, &source_id, source_is_backup);
+    let game_ids = resolve_game_selection(&source_base, &game_ids, &options.exclude_game_ids)
+        .into_iter()
+        .chain(options.extra_paths.iter().cloned());
+
+    let mut files = Vec::new();
+    for game_id in game_ids {
+        let game_path = source_base.join(&game_id);
+        let mut game_files: Vec<(String, u64)> = list_files_relative(&game_path)
+            .into_iter()
+            .filter(|(rel, _)| !(options.skip_remotecache && is_remotecache(&game_path.join(rel))))
+            .map(|(rel, metadata)| (normalize_path(&rel), metadata.len()))
+            .filter(|(path, _)| {
+                options
+                    .files
+                    .as_ref()
+                    .is_none_or(|only| only.iter().any(|f| f.replace('\\', "/") == *path))
+            })
+            .collect();
+        game_files.sort();
+        files.extend(game_files.into_iter().map(|(path, size)| SwapFile {
+            game_id: game_id.clone(),
+            path,
+            size,
+        }));
+    }
+
+    let total_files = files.len();
+    files.truncate(limit);
+    Ok(SwapFileList {
+        truncated: files.len() < total_files,
+        files,
+        total_files,
     })
 }
 
 #[tauri::command]
 fn execute_swap(
+    app: AppHandle,
     userdata_path: String,
     source_id: String,
     source_is_backup: bool,
     target_ids: Vec<String>,
     game_ids: Vec<String>,
+    options: Option<SwapOptions>,
 ) -> SwapResult {
     let ud = PathBuf::from(&userdata_path);
-    let mut details = Vec::new();
+    let options = options.unwrap_or_default();
 
-    let source_base = if source_is_backup {
-        ud.join("dunabackups").join(&source_id)
-    } else {
-        ud.join(&source_id)
-    };
-
-    // Verify at least one source game folder exists
-    let has_any_source = game_ids.iter().any(|gid| source_base.join(gid).exists());
-    if !has_any_source {
-        return SwapResult {
-            success: false,
-            message: "Source game data not found".to_string(),
-            details: vec![],
-        };
+    let valid = sanitize_profile_id(&source_id, source_is_backup)
+        .and_then(|_| validate_swap_selection(&target_ids, &game_ids, &options))
+        .and_then(|_| reject_self_target(&source_id, source_is_backup, &target_ids, &options))
+        .and_then(|_| reject_unknown_targets(&ud, &target_ids, &options));
+    if let Err(e) = valid {
+        return SwapResult::failed(e);
     }
 
-    let backups_dir = ud.join("dunabackups");
-    if let Err(e) = fs::create_dir_all(&backups_dir) {
-        return SwapResult {
-            success: false,
-            message: format!("Failed to create backups directory: {}", e),
-            details: vec![],
-        };
+    let source_base = profile_base_path(&ud, &source_id, source_is_backup);
+    log::info!(
+        "Swapping {} game(s) from {} into {:?}",
+        game_ids.len(),
+        source_base.display(),
+        target_ids
+    );
+    let Some(_guard) = SwapGuard::acquire() else {
+        return SwapResult::failed(SWAP_BUSY_MESSAGE);
+    };
+    let mut result = run_swap(&ud, &source_base, &target_ids, &game_ids, &options);
+    finish_swap(&app, &options, &mut result);
+    result
+}
+
+/// Runs several swaps as one operation: every plan is validated before anything is touched,
+/// and the batch gets a single journal entry so `undo_swap` rolls all of it back. With
+/// `stop_on_error` the first failing plan stops the batch and the plans that already ran are
+/// undone.
+#[tauri::command]
+fn execute_swap_batch(
+    app: AppHandle,
+    userdata_path: String,
+    plans: Vec<SwapPlan>,
+    stop_on_error: Option<bool>,
+) -> BatchResult {
+    let ud = PathBuf::from(&userdata_path);
+    let stop_on_error = stop_on_error.unwrap_or(false);
+    if plans.is_empty() {
+        return BatchResult::failed("No swap plans given");
     }
 
-    for target_id in &target_ids {
-        for game_id in &game_ids {
-            let source_game = source_base.join(game_id);
-            if !source_game.exists() {
-                details.push(format!(
-                    "Warning: Source has no data for game {} — skipped for target {}",
-                    game_id, target_id
+    // Undoing the batch restores targets in plan order, so with two plans writing the same
+    // target the second plan's backup (the first plan's result) would be what is left
+    let mut seen_targets = std::collections::HashSet::new();
+    for (i, plan) in plans.iter().enumerate() {
+        let valid = sanitize_profile_id(&plan.source_id, plan.source_is_backup)
+            .and_then(|_| validate_swap_selection(&plan.target_ids, &plan.game_ids, &plan.options))
+            .and_then(|_| {
+                reject_self_target(
+                    &plan.source_id,
+                    plan.source_is_backup,
+                    &plan.target_ids,
+                    &plan.options,
+                )
+            })
+            .and_then(|_| reject_unknown_targets(&ud, &plan.target_ids, &plan.options));
+        if let Err(e) = valid {
+            return BatchResult::failed(format!("Plan {}: {}", i + 1, e));
+        }
+        let targets = plan
+            .target_ids
+            .iter()
+            .map(|id| (id, false))
+            .chain(plan.options.backup_target_ids.iter().map(|id| (id, true)));
+        for target in targets {
+            if !seen_targets.insert(target) {
+                return BatchResult::failed(format!(
+                    "Plan {}: target {} is already written by an earlier plan",
+                    i + 1,
+                    target.0
                 ));
-                continue;
             }
+        }
+    }
 
-            let target_game = ud.join(target_id).join(game_id);
+    let Some(_guard) = SwapGuard::acquire() else {
+        return BatchResult::failed(SWAP_BUSY_MESSAGE);
+    };
+    let started = Instant::now();
+    let mut sources = Vec::new();
+    let mut journal_targets = Vec::new();
+    let mut results: Vec<SwapResult> = Vec::new();
+    for plan in &plans {
+        let options = SwapOptions {
+            stop_on_error: plan.options.stop_on_error || stop_on_error,
+            ..plan.options.clone()
+        };
+        let source_base = profile_base_path(&ud, &plan.source_id, plan.source_is_backup);
+        log::info!(
+            "Batch plan {}/{}: {} game(s) from {} into {:?}",
+            results.len() + 1,
+            plans.len(),
+            plan.game_ids.len(),
+            source_base.display(),
+            plan.target_ids
+        );
+        let mut result = swap_into_targets(
+            &ud,
+            &source_base,
+            &plan.target_ids,
+            &plan.game_ids,
+            &options,
+            &mut journal_targets,
+        );
+        finish_swap(&app, &options, &mut result);
+        sources.push(normalize_path(&source_base));
+        let failed = !result.success;
+        results.push(result);
+        if failed && stop_on_error {
+            break;
+        }
+    }
 
-            // Step 1: Backup existing target game data
-            if target_game.exists() {
-                let backup_game = backups_dir.join(target_id).join(game_id);
-                if backup_game.exists() {
-                    if let Err(e) = fs::remove_dir_all(&backup_game) {
-                        details.push(format!(
-                            "Warning: Failed to remove old backup for {}/{}: {}",
-                            target_id, game_id, e
-                        ));
-                    }
-                }
+    let all_success = results.iter().all(|r| r.success);
+    let mut rollback = None;
+    if !all_success && stop_on_error && !journal_targets.is_empty() {
+        let entry = new_journal_entry(sources.join("; "), std::mem::take(&mut journal_targets));
+        let undone = undo_journal_entry(&ud, &entry);
+        // Whatever couldn't be rolled back stays in the journal so it can be retried
+        if !undone.success {
+            journal_targets = entry.targets;
+        }
+        rollback = Some(undone);
+    }
 
-                if let Err(e) = fs::create_dir_all(&backup_game) {
-                    details.push(format!(
-                        "Warning: Failed to create backup dir for {}/{}: {}",
-                        target_id, game_id, e
+    let mut journal_entry_id = None;
+    if !journal_targets.is_empty() {
+        let entry = new_journal_entry(sources.join("; "), journal_targets);
+        let id = entry.id.clone();
+        match append_swap_journal(&ud, entry) {
+            Ok(()) => journal_entry_id = Some(id),
+            Err(e) => {
+                if let Some(last) = results.last_mut() {
+                    last.details.push(format!("Warning: {}", e));
+                }
+            }
+        }
+    }
+
+    let message = if all_success {
+        format!("All {} swap plans completed successfully!", results.len())
+    } else if rollback.as_ref().is_some_and(|r| r.success) {
+        "A plan failed, so the whole batch was rolled back. Check details.".to_string()
+    } else {
+        "Some operations failed. Check details.".to_string()
+    };
+    BatchResult {
+        success: all_success,
+        message,
+        plans: results,
+        rollback,
+        journal_entry_id,
+        elapsed_ms: started.elapsed().as_millis() as u64,
+    }
+}
+
+/// What the swap commands do with a finished run: record its throughput, write the requested
+/// report and tell the frontend
+fn finish_swap(app: &AppHandle, options: &SwapOptions, result: &mut SwapResult) {
+    if let Err(e) = record_swap_stats(app, result) {
+        log::warn!("Failed to record swap stats: {}", e);
+    }
+    if let Some(report_path) = &options.write_report {
+        if let Err(e) = write_swap_report(result, report_path) {
+            result.details.push(format!("Warning: {}", e));
+        }
+    }
+    emit_swap_complete(app, result);
+}
+
+/// Swapping a profile onto itself would back the folder up onto itself and then delete the
+/// data it is about to copy
+fn reject_self_target(
+    source_id: &str,
+    source_is_backup: bool,
+    target_ids: &[String],
+    options: &SwapOptions,
+) -> Result<(), String> {
+    let same_group = if source_is_backup {
+        &options.backup_target_ids
+    } else {
+        target_ids
+    };
+    if same_group.iter().any(|id| id == source_id) {
+        return Err("The source profile can't also be a target".to_string());
+    }
+    Ok(())
+}
+
+/// A target without a folder is usually a mistyped id; swapping into it would create a profile
+/// folder Steam knows nothing about
+fn reject_unknown_targets(
+    ud: &Path,
+    target_ids: &[String],
+    options: &SwapOptions,
+) -> Result<(), String> {
+    if options.allow_new_targets {
+        return Ok(());
+    }
+    let targets = target_ids
+        .iter()
+        .map(|id| (id, false))
+        .chain(options.backup_target_ids.iter().map(|id| (id, true)));
+    for (id, is_backup) in targets {
+        if !profile_base_path(ud, id, is_backup).is_dir() {
+            return Err(format!(
+                "Target profile {} doesn't exist. Allow new targets to create it.",
+                id
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn validate_swap_selection(
+    target_ids: &[String],
+    game_ids: &[String],
+    options: &SwapOptions,
+) -> Result<(), String> {
+    if !options.backup_target_ids.is_empty() && !options.allow_backup_targets {
+        return Err("Backup profiles cannot be used as targets".to_string());
+    }
+    sanitize_ids(target_ids)?;
+    options
+        .backup_target_ids
+        .iter()
+        .try_for_each(|id| sanitize_backup_id(id).map(|_| ()))?;
+    options
+        .extra_paths
+        .iter()
+        .try_for_each(|p| sanitize_relative_path(p))?;
+    if let Some(files) = &options.files {
+        if files.is_empty() {
+            return Err("No files selected".to_string());
+        }
+        files.iter().try_for_each(|f| sanitize_relative_path(f))?;
+    }
+    sanitize_game_ids(game_ids)
+}
+
+/// Set while a swap, undo or backup command writes to profiles and backups, so a second window
+/// can't start another one racing on the same folders
+static SWAP_IN_PROGRESS: AtomicBool = AtomicBool::new(false);
+
+/// Holds `SWAP_IN_PROGRESS` and clears it when dropped, early returns and panics included
+struct SwapGuard;
+
+impl SwapGuard {
+    fn acquire() -> Option<SwapGuard> {
+        SWAP_IN_PROGRESS
+            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+            .ok()
+            .map(|_| SwapGuard)
+    }
+}
+
+impl Drop for SwapGuard {
+    fn drop(&mut self) {
+        SWAP_IN_PROGRESS.store(false, Ordering::Release);
+    }
+}
+
+/// Deletes a folder being copied into when the copy panics, so the unwind doesn't leave a
+/// half-written folder behind. Does nothing when dropped normally.
+struct UnwindCleanup<'a>(&'a Path);
+
+impl Drop for UnwindCleanup<'_> {
+    fn drop(&mut self) {
+        if std::thread::panicking() {
+            log::error!("Copy into {} panicked, removing it", self.0.display());
+            let _ = fs::remove_dir_all(self.0);
+        }
+    }
+}
+
+/// Puts a target game folder back the way it was before the swap when the copy into it
+/// panics, like the `stop_on_error` rollback: restored from its backup, or removed when there
+/// is none. Does nothing when dropped normally.
+struct UnwindRestore<'a> {
+    target_game: &'a Path,
+    backup_game: Option<PathBuf>,
+    options: &'a CopyOptions,
+}
+
+impl Drop for UnwindRestore<'_> {
+    fn drop(&mut self) {
+        if !std::thread::panicking() {
+            return;
+        }
+        let restored = match &self.backup_game {
+            Some(backup_game) => {
+                let mut stats = CopyStats::default();
+                restore_game_from_backup(backup_game, self.target_game, self.options, &mut stats)
+            }
+            None => fs::remove_dir_all(self.target_game).map_err(|e| e.to_string()),
+        };
+        match restored {
+            Ok(()) => log::error!(
+                "Copy into {} panicked, put it back as it was before the swap",
+                self.target_game.display()
+            ),
+            Err(e) => log::error!(
+                "Copy into {} panicked and it could not be put back: {}",
+                self.target_game.display(),
+                e
+            ),
+        }
+    }
+}
+
+const SWAP_BUSY_MESSAGE: &str =
+    "Another swap or backup operation is still running, try again when it has finished";
+
+#[tauri::command]
+fn is_swap_in_progress() -> bool {
+    SWAP_IN_PROGRESS.load(Ordering::Acquire)
+}
+
+/// Runs `swap_into_targets` and records it in the journal. Callers hold the swap guard until
+/// `finish_swap` is done with the result.
+fn run_swap(
+    ud: &Path,
+    source_base: &Path,
+    target_ids: &[String],
+    game_ids: &[String],
+    options: &SwapOptions,
+) -> SwapResult {
+    let mut journal_targets = Vec::new();
+    let mut result = swap_into_targets(
+        ud,
+        source_base,
+        target_ids,
+        game_ids,
+        options,
+        &mut journal_targets,
+    );
+    if !journal_targets.is_empty() {
+        let entry = new_journal_entry(normalize_path(source_base), journal_targets);
+        if let Err(e) = append_swap_journal(ud, entry) {
+            result.details.push(format!("Warning: {}", e));
+        }
+    }
+    result
+}
+
+/// Swaps the selected games from `source_base` (a profile, backup or external folder) into
+/// every target: back up the target's data, clear it, then copy the source over. Every target
+/// game that was modified is added to `journal` for undoing the swap.
+fn swap_into_targets(
+    ud: &Path,
+    source_base: &Path,
+    target_ids: &[String],
+    game_ids: &[String],
+    options: &SwapOptions,
+    journal: &mut Vec<JournalTarget>,
+) -> SwapResult {
+    let started = Instant::now();
+    let mut details = Vec::new();
+    let concurrency = options.concurrency.max(1);
+    let copy_options = CopyOptions {
+        reflink: options.reflink,
+        skip_reparse_points: options.skip_reparse_points,
+        // Each operation throttles itself, so concurrent ones split the limit
+        max_bytes_per_sec: options
+            .max_bytes_per_sec
+            .filter(|&limit| limit > 0)
+            .map(|limit| (limit / concurrency as u64).max(1)),
+        skip_remotecache: options.skip_remotecache,
+        buffer_size: options.copy_buffer_size.filter(|&size| size > 0),
+        durable: options.durable,
+    };
+    // Backups must restore exactly what was there, cloud state included
+    let backup_options = CopyOptions {
+        skip_remotecache: false,
+        ..copy_options.clone()
+    };
+    let mut copy_stats = CopyStats::default();
+
+    // Callers reject the source's own id, but the same folder reached through another path
+    // (a link, different casing) would still be backed up, cleared and then copied from
+    let targets_source = target_ids
+        .iter()
+        .map(|id| profile_base_path(ud, id, false))
+        .chain(
+            options
+                .backup_target_ids
+                .iter()
+                .map(|id| profile_base_path(ud, id, true)),
+        )
+        .any(|target_base| same_folder(&target_base, source_base));
+    if targets_source {
+        return SwapResult::failed("The source profile can't also be a target");
+    }
+
+    // Extra paths go through the same backup → clear → copy steps as game folders
+    let game_ids: Vec<String> =
+        resolve_game_selection(source_base, game_ids, &options.exclude_game_ids)
+            .into_iter()
+            .chain(options.extra_paths.iter().cloned())
+            .collect();
+
+    // Verify at least one source game folder exists
+    let has_any_source = game_ids.iter().any(|gid| source_base.join(gid).exists());
+    if !has_any_source {
+        return SwapResult::failed("Source game data not found");
+    }
+
+    let backups_dir = backups_dir(ud);
+    if let Err(e) = fs::create_dir_all(&backups_dir) {
+        return SwapResult::failed(format!("Failed to create backups directory: {}", e));
+    }
+
+    // On the same volume the target's data is moved (or hard-linked) into the backup instead of
+    // copied and then deleted, which skips a full pass over the files and the extra disk space.
+    // Merging keeps the target's files in place and writes to them, so it always needs a copy.
+    let merging = matches!(options.mode, SwapMode::MergeNewer);
+    let same_volume_backup: Option<SameVolumeBackup> = match options.backup_strategy {
+        _ if merging => None,
+        BackupStrategy::Auto => Some(move_game_to_backup),
+        BackupStrategy::Hardlink => Some(link_game_to_backup),
+        BackupStrategy::Copy => None,
+    };
+    if merging {
+        details.push("Backup strategy: copy (merging keeps the target's files)".to_string());
+    } else if same_volume_backup.is_none() {
+        details.push("Backup strategy: copy (requested)".to_string());
+    }
+
+    // Every profile target gets a snapshot of its own; backup targets are backups already
+    let mut snapshots = HashMap::new();
+    for target_id in target_ids {
+        match create_backup_snapshot(&backups_dir, target_id, options.backup_label.as_deref()) {
+            Ok(snapshot) => {
+                snapshots.insert(target_id.clone(), snapshot);
+            }
+            Err(e) => {
+                remove_empty_snapshots(snapshots.values());
+                return SwapResult::failed(e);
+            }
+        }
+    }
+    // Relocated or linked profiles can be on another volume than the backups folder, so the
+    // move or link is decided for each target (and again for each game in `swap_game`)
+    if same_volume_backup.is_some() {
+        let moved = matches!(options.backup_strategy, BackupStrategy::Auto);
+        for target_id in target_ids {
+            let Some(snapshot) = snapshots.get(target_id) else {
+                continue;
+            };
+            details.push(match (same_volume(snapshot, &ud.join(target_id)), moved) {
+                (true, true) => format!(
+                    "Backup strategy for {}: move (backups folder is on the same volume)",
+                    target_id
+                ),
+                (true, false) => format!(
+                    "Backup strategy for {}: hard links (backups folder is on the same volume)",
+                    target_id
+                ),
+                (false, _) => format!(
+                    "Backup strategy for {}: copy (backups folder is on a different volume)",
+                    target_id
+                ),
+            });
+        }
+    }
+
+    let ctx = SwapContext {
+        ud,
+        source_base,
+        snapshots,
+        options,
+        copy_options,
+        backup_options,
+        merging,
+        same_volume_backup,
+    };
+    let targets: Vec<(&String, bool)> = target_ids
+        .iter()
+        .map(|id| (id, false))
+        .chain(options.backup_target_ids.iter().map(|id| (id, true)))
+        .collect();
+    // One operation per target game, target by target
+    let operations: Vec<(&String, bool, &String)> = targets
+        .iter()
+        .flat_map(|&(target_id, is_backup)| game_ids.iter().map(move |g| (target_id, is_backup, g)))
+        .collect();
+    // Set by the first failure under `stop_on_error`; operations not started yet are dropped
+    let stop = AtomicBool::new(false);
+    let run = |&(target_id, target_is_backup, game_id): &(&String, bool, &String),
+               copy_from: Option<&Path>| {
+        let outcome = swap_game(&ctx, target_id, target_is_backup, game_id, copy_from);
+        if options.stop_on_error && outcome.failed() {
+            stop.store(true, Ordering::Release);
+        }
+        outcome
+    };
+
+    let mut outcomes: Vec<Option<GameOutcome>> = Vec::new();
+    if concurrency == 1 {
+        // Game folders already written to a target during this run. Later targets copy from
+        // that materialized copy instead of re-reading the source; if that fails we fall back to
+        // a plain copy from the source. Only done sequentially, where no operation can still be
+        // writing the copy.
+        let mut materialized: HashMap<&String, PathBuf> = HashMap::new();
+        for operation in &operations {
+            if stop.load(Ordering::Acquire) {
+                break;
+            }
+            let game_id = operation.2;
+            let outcome = run(operation, materialized.get(game_id).map(PathBuf::as_path));
+            if outcome.swapped() && options.files.is_none() {
+                materialized
+                    .entry(game_id)
+                    .or_insert_with(|| outcome.target_game.clone());
+            }
+            outcomes.push(Some(outcome));
+        }
+    } else {
+        let next = AtomicUsize::new(0);
+        let finished: Vec<(usize, GameOutcome)> = std::thread::scope(|scope| {
+            let workers: Vec<_> = (0..concurrency.min(operations.len()))
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut done = Vec::new();
+                        while !stop.load(Ordering::Acquire) {
+                            let i = next.fetch_add(1, Ordering::AcqRel);
+                            let Some(operation) = operations.get(i) else {
+                                break;
+                            };
+                            done.push((i, run(operation, None)));
+                        }
+                        done
+                    })
+                })
+                .collect();
+            workers
+                .into_iter()
+                .flat_map(|w| w.join().unwrap_or_else(|e| std::panic::resume_unwind(e)))
+                .collect()
+        });
+        outcomes.resize_with(operations.len(), || None);
+        for (i, outcome) in finished {
+            outcomes[i] = Some(outcome);
+        }
+    }
+
+    // Outcomes are collected in operation order, so the details read the same whatever order
+    // the operations finished in
+    let mut games_swapped = 0;
+    let mut targets_affected = 0;
+    let mut bytes_copied: u64 = 0;
+    let mut files_copied = 0;
+    let mut target_results = Vec::new();
+    let mut drives = None;
+    let mut outcomes = outcomes.into_iter();
+    for &(target_id, is_backup) in &targets {
+        let target_outcomes: Vec<GameOutcome> =
+            outcomes.by_ref().take(game_ids.len()).flatten().collect();
+        if target_outcomes.is_empty() {
+            continue;
+        }
+
+        let mut statuses = Vec::new();
+        let mut journal_games = Vec::new();
+        let swapped_before = games_swapped;
+        let mut target_bytes = 0;
+        let mut target_copy_time = Duration::ZERO;
+        for outcome in target_outcomes {
+            if outcome.swapped() {
+                games_swapped += 1;
+                bytes_copied += outcome.bytes_copied;
+                files_copied += outcome.files_copied;
+                target_bytes += outcome.bytes_copied;
+                target_copy_time += outcome.copy_time;
+            }
+            details.extend(outcome.details);
+            copy_stats.absorb(outcome.stats);
+            journal_games.extend(outcome.journal);
+            statuses.push(GameSwapStatus {
+                game_id: outcome.game_id,
+                status: outcome.status,
+            });
+        }
+
+        if games_swapped > swapped_before {
+            targets_affected += 1;
+        }
+        // Later estimates for this drive use the rate the swap actually copied at
+        if target_bytes >= BENCHMARK_BYTES {
+            let drives = drives.get_or_insert_with(Drives::list);
+            let drive = drive_label(&profile_base_path(ud, target_id, is_backup), drives);
+            record_copy_throughput(&drive, target_bytes, target_copy_time);
+        }
+        target_results.push(TargetResult {
+            target_id: (*target_id).clone(),
+            target_name: get_persona_name(ud, backup_account_id(target_id)),
+            games: statuses,
+        });
+        if !journal_games.is_empty() {
+            journal.push(JournalTarget {
+                target_id: (*target_id).clone(),
+                backup: ctx
+                    .snapshots
+                    .get(target_id)
+                    .and_then(|s| s.file_name())
+                    .map(|name| name.to_string_lossy().to_string()),
+                games: journal_games,
+            });
+        }
+    }
+    // Targets that had nothing to back up leave their snapshot empty, labeled or not
+    remove_empty_snapshots(ctx.snapshots.values());
+    if stop.load(Ordering::Acquire) {
+        details.push("Stopped after the first error, remaining games were not swapped".to_string());
+    }
+
+    if copy_stats.retries > 0 {
+        details.push(format!(
+            "Retried {} file operation(s) after transient failures (file in use)",
+            copy_stats.retries
+        ));
+    }
+
+    for link in &copy_stats.skipped_links {
+        details.push(format!(
+            "Skipped link (symlink/junction) {}",
+            normalize_path(link)
+        ));
+    }
+
+    if copy_stats.skipped_remotecaches > 0 {
+        details.push(format!(
+            "Skipped {} remotecache.vdf file(s) so Steam Cloud rebuilds its sync state for each target",
+            copy_stats.skipped_remotecaches
+        ));
+    }
+    if !options.skip_remotecache && games_swapped > 0 {
+        details.push(
+            "Kept the source's remotecache.vdf: targets inherit its Steam Cloud state, so Steam won't re-upload the saves, but it may replace them with the cloud copy if the target's cloud files differ"
+                .to_string(),
+        );
+    }
+
+    if ctx.copy_options.reflink {
+        details.push(format!(
+            "Reflinked {} file(s), copied {} file(s)",
+            copy_stats.files_reflinked, copy_stats.files_copied
+        ));
+    }
+
+    let all_success = !details.iter().any(|d| d.starts_with("Error:"));
+
+    let result = SwapResult {
+        success: all_success,
+        message: if all_success {
+            "All games swapped successfully!".to_string()
+        } else {
+            "Some operations failed. Check details.".to_string()
+        },
+        details,
+        games_swapped,
+        targets_affected,
+        bytes_copied,
+        files_copied,
+        elapsed_ms: started.elapsed().as_millis() as u64,
+        bytes_written: copy_stats.bytes_copied,
+        targets: target_results,
+    };
+    log::info!(
+        "Swap finished: {} ({} game(s) swapped, {} bytes in {} ms)",
+        result.message,
+        result.games_swapped,
+        result.bytes_copied,
+        result.elapsed_ms
+    );
+    for detail in result.details.iter().filter(|d| d.starts_with("Error:")) {
+        log::error!("{}", detail);
+    }
+    result
+}
+
+type SameVolumeBackup = fn(&Path, &Path, &str, &str, &mut CopyStats, &mut Vec<String>) -> bool;
+
+/// What every game operation of a swap shares
+struct SwapContext<'a> {
+    ud: &'a Path,
+    source_base: &'a Path,
+    /// The snapshot each profile target is backed up into, by target id
+    snapshots: HashMap<String, PathBuf>,
+    options: &'a SwapOptions,
+    copy_options: CopyOptions,
+    backup_options: CopyOptions,
+    merging: bool,
+    /// Moves or hard-links a target game into its snapshot when both are on the same volume;
+    /// `None` always copies
+    same_volume_backup: Option<SameVolumeBackup>,
+}
+
+/// One target game's part of a swap, with its own log and stats so operations can run
+/// concurrently and be merged afterwards
+struct GameOutcome {
+    game_id: String,
+    target_game: PathBuf,
+    status: SwapStatus,
+    details: Vec<String>,
+    stats: CopyStats,
+    /// Written into the target (backups not included)
+    bytes_copied: u64,
+    files_copied: usize,
+    /// Spent copying into the target, for `record_copy_throughput`
+    copy_time: Duration,
+    /// Set once the target was modified, so undoing the swap has to touch it
+    journal: Option<JournalGame>,
+}
+
+impl GameOutcome {
+    fn swapped(&self) -> bool {
+        matches!(self.status, SwapStatus::Swapped)
+    }
+
+    fn failed(&self) -> bool {
+        self.details.iter().any(|d| d.starts_with("Error:"))
+    }
+}
+
+/// Backs up one target game, clears it and copies the source's folder over (from `copy_from`
+/// when given, an earlier target's copy). Under `stop_on_error` a game that fails is rolled back
+/// from the backup taken for it.
+fn swap_game(
+    ctx: &SwapContext,
+    target_id: &str,
+    target_is_backup: bool,
+    game_id: &str,
+    copy_from: Option<&Path>,
+) -> GameOutcome {
+    let options = ctx.options;
+    let source_game = ctx.source_base.join(game_id);
+    let target_game = profile_base_path(ctx.ud, target_id, target_is_backup).join(game_id);
+    // Backup targets have no snapshot, their old contents aren't kept (see step 1)
+    let snapshot = ctx
+        .snapshots
+        .get(target_id)
+        .filter(|_| !target_is_backup)
+        .map(PathBuf::as_path);
+    // Checked before step 1, which moves the folder away under the move strategy
+    let had_data = target_game.exists();
+    let mut details = Vec::new();
+    let mut copy_stats = CopyStats::default();
+    let mut journal = None;
+    let mut bytes_copied = 0;
+    let mut files_copied = 0;
+    let mut copy_time = Duration::ZERO;
+
+    let status = 'swap: {
+        if !source_game.exists() {
+            details.push(format!(
+                "Warning: Source has no data for game {} — skipped for target {}",
+                game_id, target_id
+            ));
+            break 'swap SwapStatus::Skipped;
+        }
+
+        if let Some(files) = &options.files {
+            let mut swapped = Vec::new();
+            let swap_result = swap_game_files(
+                ctx,
+                &source_game,
+                &target_game,
+                snapshot.map(|s| (s, game_id)),
+                files,
+                &mut copy_stats,
+                &mut swapped,
+            );
+            if !target_is_backup && !swapped.is_empty() {
+                journal = Some(JournalGame {
+                    game_id: game_id.to_string(),
+                    had_data,
+                    files: Some(swapped),
+                });
+            }
+            break 'swap match swap_result {
+                Ok(count) => {
+                    bytes_copied = copy_stats.bytes_copied;
+                    files_copied = count;
+                    details.push(format!(
+                        "Successfully swapped {} file(s) of game {} for profile {}",
+                        count, game_id, target_id
                     ));
-                    continue;
+                    SwapStatus::Swapped
+                }
+                Err(e) => {
+                    details.push(format!(
+                        "Error: Failed to swap files of game {} to {}: {}",
+                        game_id, target_id, e
+                    ));
+                    SwapStatus::Failed(e)
                 }
+            };
+        }
+
+        // Step 1: Backup existing target game data. A backup is itself the backup location,
+        // so its previous contents are overwritten without another copy.
+        if target_is_backup && target_game.exists() {
+            details.push(format!(
+                "Warning: Overwriting backup {}/{} without keeping its previous contents",
+                target_id, game_id
+            ));
+        } else if target_game.exists()
+            && !snapshot.is_some_and(|snapshot| {
+                // Moving and linking fall back to copying when they fail
+                let same_volume_backup = ctx
+                    .same_volume_backup
+                    .filter(|_| same_volume(snapshot, &target_game));
+                same_volume_backup.is_some_and(|backup| {
+                    backup(
+                        snapshot,
+                        &target_game,
+                        target_id,
+                        game_id,
+                        &mut copy_stats,
+                        &mut details,
+                    )
+                }) || backup_game_folder(
+                    snapshot,
+                    &target_game,
+                    target_id,
+                    game_id,
+                    &ctx.backup_options,
+                    &mut copy_stats,
+                    &mut details,
+                )
+            })
+        {
+            break 'swap SwapStatus::Failed("Backup failed".to_string());
+        }
+        // From here on the target is modified, so undoing the swap has to touch it
+        if !target_is_backup {
+            journal = Some(JournalGame {
+                game_id: game_id.to_string(),
+                had_data,
+                files: None,
+            });
+        }
 
-                match copy_dir_recursive(&target_game, &backup_game) {
-                    Ok(_) => details.push(format!(
-                        "Backed up game {} for profile {} to dunabackups",
-                        game_id, target_id
-                    )),
-                    Err(e) => {
+        // Step 2: Delete target game folder
+        if !ctx.merging && target_game.exists() {
+            if let Err(e) = retry_io(&mut copy_stats.retries, || fs::remove_dir_all(&target_game)) {
+                details.push(format!(
+                    "Error: Failed to clear target {}/{}: {}",
+                    target_id, game_id, e
+                ));
+                // A hard-linked backup still shares its files with what is left of the target
+                if let Some(snapshot) = snapshot {
+                    if let Err(e) = unlink_backup_from_target(&snapshot.join(game_id), &target_game)
+                    {
                         details.push(format!(
-                            "Warning: Backup failed for {}/{}: {}",
+                            "Warning: Backup of {}/{} still shares files with the target: {}",
                             target_id, game_id, e
                         ));
-                        continue;
                     }
                 }
+                break 'swap SwapStatus::BackedUpOnly;
+            }
+        }
+
+        // Step 3: Copy source game folder to target
+        if let Err(e) = fs::create_dir_all(&target_game) {
+            details.push(format!(
+                "Error: Failed to create target dir for {}/{}: {}",
+                target_id, game_id, e
+            ));
+            break 'swap SwapStatus::Failed(e.to_string());
+        }
+
+        // A copy that panics puts back the target's data from the snapshot. Without one the
+        // folder only held what the copy wrote, unless merging into the target's own files.
+        let backup_game = snapshot.filter(|_| had_data).map(|s| s.join(game_id));
+        let _restore =
+            (backup_game.is_some() || !had_data || !ctx.merging).then(|| UnwindRestore {
+                target_game: &target_game,
+                backup_game,
+                options: &ctx.backup_options,
+            });
+        let bytes_before = copy_stats.bytes_copied;
+        let files_before = copy_stats.files_copied + copy_stats.files_reflinked;
+        let copy_options = &ctx.copy_options;
+        let copy_started = Instant::now();
+        let copy_result = match copy_from {
+            _ if ctx.merging => merge_game_files(
+                &source_game,
+                &target_game,
+                MergeStrategy::NewerWins,
+                copy_options,
+                &mut copy_stats,
+            )
+            .map(|(copied, kept)| {
+                details.push(format!(
+                    "Merged game {} into {}: {} file(s) copied, {} kept",
+                    game_id, target_id, copied, kept
+                ))
+            }),
+            Some(copy_from) => copy_dir_recursive(
+                copy_from,
+                &target_game,
+                copy_options,
+                &mut copy_stats,
+            )
+            .or_else(|_| {
+                let _ = fs::remove_dir_all(&target_game);
+                copy_dir_recursive(&source_game, &target_game, copy_options, &mut copy_stats)
+            }),
+            None => copy_dir_recursive(&source_game, &target_game, copy_options, &mut copy_stats),
+        };
+        copy_time = copy_started.elapsed();
+
+        match copy_result {
+            Ok(_) => {
+                bytes_copied = copy_stats.bytes_copied - bytes_before;
+                files_copied = copy_stats.files_copied + copy_stats.files_reflinked - files_before;
+                details.push(format!(
+                    "Successfully swapped game {} for profile {}",
+                    game_id, target_id
+                ));
+                SwapStatus::Swapped
+            }
+            Err(e) => {
+                details.push(format!(
+                    "Error: Failed to copy game {} to {}: {}",
+                    game_id, target_id, e
+                ));
+                SwapStatus::Failed(e)
+            }
+        }
+    };
+
+    let failed = details.iter().any(|d| d.starts_with("Error:"));
+    let rollback = journal.as_ref().filter(|_| options.stop_on_error && failed);
+    if let (Some(game), Some(snapshot)) = (rollback, snapshot) {
+        let rolled_back = revert_journal_game(
+            &snapshot.join(game_id),
+            &target_game,
+            game,
+            &ctx.backup_options,
+            &mut copy_stats,
+        );
+        match rolled_back {
+            Ok(_) => {
+                journal = None;
+                details.push(format!(
+                    "Rolled back game {} for profile {} to its state before the swap",
+                    game_id, target_id
+                ));
             }
+            Err(e) => details.push(format!(
+                "Error: Failed to roll back game {} for profile {}: {}",
+                game_id, target_id, e
+            )),
+        }
+    }
+
+    GameOutcome {
+        game_id: game_id.to_string(),
+        target_game,
+        status,
+        details,
+        stats: copy_stats,
+        bytes_copied,
+        files_copied,
+        copy_time,
+        journal,
+    }
+}
+
+fn format_swap_report(result: &SwapResult) -> String {
+    let mut report = String::from("# Swap report\n\n");
+    report.push_str(&format!(
+        "- **Generated:** {}\n- **Result:** {}\n- **Message:** {}\n- **Games swapped:** {}\n- **Targets affected:** {}\n- **Bytes copied:** {}\n- **Duration:** {} ms\n",
+        format_system_time(SystemTime::now()),
+        if result.success { "Success" } else { "Failed" },
+        result.message,
+        result.games_swapped,
+        result.targets_affected,
+        result.bytes_copied,
+        result.elapsed_ms,
+    ));
+
+    for target in &result.targets {
+        report.push_str(&format!(
+            "\n## {} ({})\n\n| Game | Status |\n| --- | --- |\n",
+            target.target_name, target.target_id
+        ));
+        for game in &target.games {
+            let status = match &game.status {
+                SwapStatus::Swapped => "Swapped".to_string(),
+                SwapStatus::Skipped => "Skipped".to_string(),
+                SwapStatus::BackedUpOnly => "Backed up only".to_string(),
+                SwapStatus::Failed(reason) => format!("Failed: {}", reason.replace('|', "\\|")),
+            };
+            report.push_str(&format!("| {} | {} |\n", game.game_id, status));
+        }
+    }
+
+    if !result.details.is_empty() {
+        report.push_str("\n## Details\n\n");
+        for detail in &result.details {
+            report.push_str(&format!("- {}\n", detail));
+        }
+    }
+
+    report
+}
+
+fn emit_swap_complete(app: &AppHandle, result: &SwapResult) {
+    let count_prefixed = |prefix: &str| {
+        result
+            .details
+            .iter()
+            .filter(|d| d.starts_with(prefix))
+            .count()
+    };
+
+    let _ = app.emit(
+        "swap-complete",
+        SwapCompleteEvent {
+            success: result.success,
+            games_swapped: result.games_swapped,
+            targets_affected: result.targets_affected,
+            bytes_copied: result.bytes_copied,
+            elapsed_ms: result.elapsed_ms,
+            warnings: count_prefixed("Warning:"),
+            errors: count_prefixed("Error:"),
+        },
+    );
+}
+
+#[tauri::command]
+fn import_as_source(
+    app: AppHandle,
+    userdata_path: String,
+    external_path: String,
+    target_ids: Vec<String>,
+    game_ids: Vec<String>,
+    options: Option<SwapOptions>,
+) -> SwapResult {
+    let ud = PathBuf::from(&userdata_path);
+    let options = options.unwrap_or_default();
+    let source_base = PathBuf::from(&external_path);
+
+    let valid = validate_swap_selection(&target_ids, &game_ids, &options)
+        .and_then(|_| reject_unknown_targets(&ud, &target_ids, &options));
+    if let Err(e) = valid {
+        return SwapResult::failed(e);
+    }
+
+    // The folder has to look like userdata/<id>: numeric game folders with actual data in them
+    if !source_base.is_dir() || list_game_folder_ids(&source_base).is_empty() {
+        return SwapResult::failed("The selected folder doesn't look like a Steam profile folder. It should contain numbered game folders (e.g. 570, 730).");
+    }
+
+    let Some(_guard) = SwapGuard::acquire() else {
+        return SwapResult::failed(SWAP_BUSY_MESSAGE);
+    };
+    let mut result = run_swap(&ud, &source_base, &target_ids, &game_ids, &options);
+    finish_swap(&app, &options, &mut result);
+    result
+}
+
+/// Copies games from an existing profile into a new account folder, e.g. to set up an alt
+/// account that hasn't logged in on this machine yet
+#[tauri::command]
+fn clone_profile(
+    app: AppHandle,
+    userdata_path: String,
+    source_id: String,
+    new_id: String,
+    game_ids: Vec<String>,
+) -> SwapResult {
+    if let Err(e) = sanitize_ids([&source_id, &new_id]).and_then(|_| sanitize_game_ids(&game_ids)) {
+        return SwapResult::failed(e);
+    }
+    if game_ids.is_empty() {
+        return SwapResult::failed("No games selected");
+    }
+
+    let ud = PathBuf::from(&userdata_path);
+    let source_base = ud.join(&source_id);
+    if !source_base.is_dir() {
+        return SwapResult::failed("Source profile not found");
+    }
+    let Some(_guard) = SwapGuard::acquire() else {
+        return SwapResult::failed(SWAP_BUSY_MESSAGE);
+    };
+    let new_base = ud.join(&new_id);
+    if new_base.exists() {
+        return SwapResult::failed(format!("Profile {} already exists", new_id));
+    }
+
+    // Steam creates the rest of the account folder on first login
+    if let Err(e) = fs::create_dir_all(new_base.join("config")) {
+        return SwapResult::failed(format!("Failed to create profile folder: {}", e));
+    }
+
+    let options = SwapOptions::default();
+    let mut result = run_swap(
+        &ud,
+        &source_base,
+        std::slice::from_ref(&new_id),
+        &game_ids,
+        &options,
+    );
+    finish_swap(&app, &options, &mut result);
+    result
+}
+
+/// Writes a swap result as a markdown audit trail, or as JSON for a `.json` path
+#[tauri::command]
+fn export_swap_report(result: SwapResult, out_path: String) -> Result<(), String> {
+    write_swap_report(&result, &out_path)
+}
+
+fn write_swap_report(result: &SwapResult, out_path: &str) -> Result<(), String> {
+    let is_json = Path::new(out_path)
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+    let content = if is_json {
+        serde_json::to_string_pretty(result).map_err(|e| e.to_string())?
+    } else {
+        format_swap_report(result)
+    };
+    fs::write(out_path, content)
+        .map_err(|e| format!("Failed to write report to {}: {}", out_path, e))
+}
+
+/// Merges every game of `from_id` into `into_id` file by file, e.g. for two account folders
+/// left behind by a reinstall. Target game folders that may be overwritten are backed up first.
+#[tauri::command]
+fn merge_profiles(
+    userdata_path: String,
+    from_id: String,
+    into_id: String,
+    strategy: MergeStrategy,
+) -> SwapResult {
+    if let Err(e) = sanitize_ids([&from_id, &into_id]) {
+        return SwapResult::failed(e);
+    }
+    if from_id == into_id {
+        return SwapResult::failed("Cannot merge a profile into itself");
+    }
+    let Some(_guard) = SwapGuard::acquire() else {
+        return SwapResult::failed(SWAP_BUSY_MESSAGE);
+    };
+
+    let ud = PathBuf::from(&userdata_path);
+    let from_path = ud.join(&from_id);
+    let into_path = ud.join(&into_id);
+    if !from_path.is_dir() || !into_path.is_dir() {
+        return SwapResult::failed("Profile not found");
+    }
+
+    let snapshot = match create_backup_snapshot(&backups_dir(&ud), &into_id, None) {
+        Ok(snapshot) => snapshot,
+        Err(e) => return SwapResult::failed(e),
+    };
+
+    let copy_options = CopyOptions::default();
+    let mut stats = CopyStats::default();
+    let mut details = Vec::new();
+
+    for game_id in list_game_folder_ids(&from_path) {
+        let source_game = from_path.join(&game_id);
+        let target_game = into_path.join(&game_id);
+
+        let may_overwrite = !matches!(strategy, MergeStrategy::SkipExisting);
+        if may_overwrite
+            && target_game.exists()
+            && !backup_game_folder(
+                &snapshot,
+                &target_game,
+                &into_id,
+                &game_id,
+                &copy_options,
+                &mut stats,
+                &mut details,
+            )
+        {
+            details.push(format!(
+                "Error: Game {} was not merged because the backup failed",
+                game_id
+            ));
+            continue;
+        }
+
+        match merge_game_files(
+            &source_game,
+            &target_game,
+            strategy,
+            &copy_options,
+            &mut stats,
+        ) {
+            Ok((copied, kept)) => details.push(format!(
+                "Merged game {}: {} file(s) copied, {} kept",
+                game_id, copied, kept
+            )),
+            Err(e) => details.push(format!("Error: Failed to merge game {}: {}", game_id, e)),
+        }
+    }
+    remove_empty_snapshots([&snapshot]);
+
+    let all_success = !details.iter().any(|d| d.starts_with("Error:"));
+
+    SwapResult {
+        success: all_success,
+        message: if all_success {
+            "Profiles merged successfully!".to_string()
+        } else {
+            "Some games failed to merge. Check details.".to_string()
+        },
+        details,
+        ..Default::default()
+    }
+}
+
+/// Moves a save to another appid of the same profile, for games that exist under several
+/// appids (regional variants, reissues). Both folders are backed up first; an existing save
+/// under `to_game_id` is only replaced when `overwrite` is set.
+#[tauri::command]
+fn relocate_game_save(
+    userdata_path: String,
+    profile_id: String,
+    from_game_id: String,
+    to_game_id: String,
+    overwrite: bool,
+) -> SwapResult {
+    if let Err(e) = sanitize_ids([&profile_id, &from_game_id, &to_game_id]) {
+        return SwapResult::failed(e);
+    }
+    if from_game_id == to_game_id {
+        return SwapResult::failed("Cannot move a save onto the same game");
+    }
+    let Some(_guard) = SwapGuard::acquire() else {
+        return SwapResult::failed(SWAP_BUSY_MESSAGE);
+    };
+
+    let ud = PathBuf::from(&userdata_path);
+    let profile_path = ud.join(&profile_id);
+    let from_game = profile_path.join(&from_game_id);
+    let to_game = profile_path.join(&to_game_id);
+    if !from_game.is_dir() {
+        return SwapResult::failed(format!("Profile has no data for game {}", from_game_id));
+    }
+    if to_game.exists() && !overwrite {
+        return SwapResult::failed(format!(
+            "Game {} already has a save; allow overwriting to replace it",
+            to_game_id
+        ));
+    }
+
+    let snapshot = match create_backup_snapshot(&backups_dir(&ud), &profile_id, None) {
+        Ok(snapshot) => snapshot,
+        Err(e) => return SwapResult::failed(e),
+    };
+
+    let options = CopyOptions::default();
+    let mut stats = CopyStats::default();
+    let mut details = Vec::new();
+    for (game_id, game_path) in [(&from_game_id, &from_game), (&to_game_id, &to_game)] {
+        let backed_up = !game_path.exists()
+            || backup_game_folder(
+                &snapshot,
+                game_path,
+                &profile_id,
+                game_id,
+                &options,
+                &mut stats,
+                &mut details,
+            );
+        if !backed_up {
+            remove_empty_snapshots([&snapshot]);
+            return SwapResult {
+                details,
+                ..SwapResult::failed("The save was not moved because the backup failed")
+            };
+        }
+    }
+
+    if to_game.exists() {
+        if let Err(e) = retry_io(&mut stats.retries, || fs::remove_dir_all(&to_game)) {
+            return SwapResult {
+                details,
+                ..SwapResult::failed(format!("Failed to clear game {}: {}", to_game_id, e))
+            };
+        }
+    }
+
+    // A rename within the profile folder is the common case; copying covers the rest
+    let moved = fs::rename(&from_game, &to_game).or_else(|_| {
+        copy_dir_recursive(&from_game, &to_game, &options, &mut stats)
+            .and_then(|_| fs::remove_dir_all(&from_game).map_err(|e| e.to_string()))
+            .map_err(io::Error::other)
+    });
+    if let Err(e) = moved {
+        return SwapResult {
+            details,
+            ..SwapResult::failed(format!("Failed to move the save: {}", e))
+        };
+    }
+
+    details.push(format!(
+        "Moved the save of game {} to game {} for profile {}",
+        from_game_id, to_game_id, profile_id
+    ));
+    SwapResult {
+        success: true,
+        message: "Save moved successfully!".to_string(),
+        details,
+        games_swapped: 1,
+        targets_affected: 1,
+        ..Default::default()
+    }
+}
+
+/// Swaps single files of a game folder. With a backup snapshot given as (snapshot, game id),
+/// each target file that exists is first copied into the snapshot's game folder. Every file is added to `swapped` before its target is written, so a
+/// swap that fails partway can still be rolled back. Returns the number of files swapped.
+fn swap_game_files(
+    ctx: &SwapContext,
+    source_game: &Path,
+    target_game: &Path,
+    backup: Option<(&Path, &str)>,
+    files: &[String],
+    stats: &mut CopyStats,
+    swapped: &mut Vec<JournalFile>,
+) -> Result<usize, String> {
+    let create_parent = |path: &Path| {
+        path.parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .map_err(|e| format!("Failed to create dir for {:?}: {}", path, e))
+    };
+
+    let mut backed_up = false;
+    for file in files {
+        let src = source_game.join(file);
+        if !src.is_file() {
+            return Err(format!("Source has no file {}", file));
+        }
+
+        let dst = target_game.join(file);
+        let had_data = dst.is_file();
+        if let Some((backup_path, game_id)) = backup.filter(|_| had_data) {
+            let backup_file = backup_path.join(game_id).join(file);
+            create_parent(&backup_file)?;
+            replace_file(&dst, &backup_file, &ctx.backup_options, stats)?;
+            backed_up = true;
+        }
+        swapped.push(JournalFile {
+            path: file.clone(),
+            had_data,
+        });
+
+        create_parent(&dst)?;
+        replace_file(&src, &dst, &ctx.copy_options, stats)?;
+    }
+
+    if let Some((backup_path, game_id)) = backup.filter(|_| backed_up) {
+        record_backup_in_manifest(backup_path, game_id)?;
+    }
+    Ok(files.len())
+}
+
+/// Copies source files into the target game folder file by file, never deleting anything in
+/// the target. Returns how many files were copied and how many target files were kept.
+fn merge_game_files(
+    source_game: &Path,
+    target_game: &Path,
+    strategy: MergeStrategy,
+    options: &CopyOptions,
+    stats: &mut CopyStats,
+) -> Result<(usize, usize), String> {
+    let target_files = list_files_relative(target_game);
+    let mut copied = 0;
+    let mut kept = 0;
+    for (rel, source_meta) in list_files_relative(source_game) {
+        let src = source_game.join(&rel);
+        if options.skip_remotecache && is_remotecache(&src) {
+            stats.skipped_remotecaches += 1;
+            continue;
+        }
+
+        let overwrite = match (target_files.get(&rel), strategy) {
+            (None, _) => true,
+            (Some(_), MergeStrategy::SourceWins) => true,
+            (Some(_), MergeStrategy::SkipExisting) => false,
+            (Some(target_meta), MergeStrategy::NewerWins) => {
+                source_meta.modified().ok() > target_meta.modified().ok()
+            }
+        };
+        if !overwrite {
+            kept += 1;
+            continue;
+        }
+
+        let dst = target_game.join(&rel);
+        dst.parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .map_err(|e| e.to_string())
+            .and_then(|_| replace_file(&src, &dst, options, stats))?;
+        copied += 1;
+    }
+    Ok((copied, kept))
+}
+
+// ─── Backups ────────────────────────────────────────────────────────
+
+const BACKUP_MANIFEST_FILE: &str = "backup.json";
+
+/// Lives at `dunabackups/<id>/<snapshot>/backup.json`, next to (not inside) the backed up game
+/// folders
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct BackupManifest {
+    games: HashMap<String, BackupManifestEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BackupManifestEntry {
+    /// Unix timestamp (seconds) of when the backup was taken
+    created: u64,
+    file_count: usize,
+    total_size: u64,
+}
+
+fn read_backup_manifest(backup_path: &Path) -> BackupManifest {
+    fs::read_to_string(backup_path.join(BACKUP_MANIFEST_FILE))
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+/// Concurrent swap operations can back up several games of the same target at once
+static BACKUP_MANIFEST_LOCK: Mutex<()> = Mutex::new(());
+
+fn record_backup_in_manifest(backup_path: &Path, game_id: &str) -> Result<(), String> {
+    let (total_size, file_count, _, _) = get_dir_stats(&backup_path.join(game_id));
+    let created = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    let _lock = BACKUP_MANIFEST_LOCK.lock().unwrap();
+    let mut manifest = read_backup_manifest(backup_path);
+    manifest.games.insert(
+        game_id.to_string(),
+        BackupManifestEntry {
+            created,
+            file_count,
+            total_size,
+        },
+    );
+
+    write_backup_manifest(backup_path, &manifest)
+}
+
+fn write_backup_manifest(backup_path: &Path, manifest: &BackupManifest) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(manifest).map_err(|e| e.to_string())?;
+    fs::write(backup_path.join(BACKUP_MANIFEST_FILE), json)
+        .map_err(|e| format!("Failed to write backup manifest: {}", e))
+}
+
+// ─── Backup snapshots ───────────────────────────────────────────────
+
+/// Snapshot folders are named after the (UTC) time they were taken
+const SNAPSHOT_NAME_FORMAT: &str = "%Y%m%d-%H%M%S";
+
+/// One backup run of an account, `dunabackups/<account_id>/<name>/<game_id>`. Every swap and
+/// backup writes a new snapshot, so earlier backups are never replaced.
+#[derive(Debug, Clone)]
+struct BackupSnapshot {
+    account_id: String,
+    name: String,
+    path: PathBuf,
+    /// Unix timestamp (seconds) of when the snapshot was taken, unique per account
+    created: u64,
+    label: Option<String>,
+}
+
+impl BackupSnapshot {
+    fn from_path(path: &Path) -> Option<BackupSnapshot> {
+        let account_id = numeric_folder_name(path.parent()?)?;
+        let name = path.file_name()?.to_str()?.to_string();
+        let (created, label) = parse_snapshot_name(&name)?;
+        Some(BackupSnapshot {
+            account_id,
+            name,
+            path: path.to_path_buf(),
+            created,
+            label,
+        })
+    }
+
+    /// The profile id of a backup, `<account_id>/<name>`
+    fn id(&self) -> String {
+        format!("{}/{}", self.account_id, self.name)
+    }
+}
+
+/// `<time>` or `<time>_<label>`, with a label already passed through `sanitize_backup_label`
+fn snapshot_name(created: u64, label: Option<&str>) -> String {
+    let time = chrono::DateTime::from_timestamp(created as i64, 0)
+        .unwrap_or_default()
+        .format(SNAPSHOT_NAME_FORMAT);
+    match label {
+        Some(label) => format!("{}_{}", time, label),
+        None => time.to_string(),
+    }
+}
+
+/// The time and label of a snapshot folder name
+fn parse_snapshot_name(name: &str) -> Option<(u64, Option<String>)> {
+    let (time, label) = match name.split_once('_') {
+        Some((_, "")) => return None,
+        Some((time, label)) => (time, Some(label)),
+        None => (name, None),
+    };
+    let created = chrono::NaiveDateTime::parse_from_str(time, SNAPSHOT_NAME_FORMAT).ok()?;
+    let created = u64::try_from(created.and_utc().timestamp()).ok()?;
+    Some((created, label.map(str::to_string)))
+}
+
+/// The account part of a backup id; regular profile ids are returned as they are
+fn backup_account_id(id: &str) -> &str {
+    id.split_once('/').map_or(id, |(account_id, _)| account_id)
+}
+
+/// Creates an empty snapshot folder for `account_id`, named with the sanitized `label` if one
+/// is given. Snapshots taken within the same second are pushed to the next free one, so an
+/// account's snapshots are told apart by their time alone.
+fn create_backup_snapshot(
+    backups_dir: &Path,
+    account_id: &str,
+    label: Option<&str>,
+) -> Result<PathBuf, String> {
+    let account_path = backups_dir.join(account_id);
+    fs::create_dir_all(&account_path)
+        .map_err(|e| format!("Failed to create backups directory: {}", e))?;
+    let now = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    create_snapshot_dir(&account_path, now, label)
+}
+
+fn create_snapshot_dir(
+    account_path: &Path,
+    created: u64,
+    label: Option<&str>,
+) -> Result<PathBuf, String> {
+    let label = label.and_then(sanitize_backup_label);
+    let taken: Vec<u64> = read_account_snapshots(account_path)
+        .iter()
+        .map(|s| s.created)
+        .collect();
+    for created in (created..created + 60).filter(|t| !taken.contains(t)) {
+        let path = account_path.join(snapshot_name(created, label.as_deref()));
+        match fs::create_dir(&path) {
+            Ok(()) => return Ok(path),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(format!("Failed to create backup folder: {}", e)),
+        }
+    }
+    Err("Failed to create backup folder: too many backups in the last minute".to_string())
+}
+
+/// Backups used to be a single slot per account, `dunabackups/<account_id>/<game_id>`. Moves
+/// such a slot into a snapshot of its own, dated by its newest manifest entry and named with
+/// its label.
+fn migrate_legacy_backup(account_path: &Path) {
+    let Ok(entries) = fs::read_dir(account_path) else {
+        return;
+    };
+    let legacy_games: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_dir() && numeric_folder_name(p).is_some())
+        .collect();
+    if legacy_games.is_empty() {
+        return;
+    }
+
+    let manifest = read_backup_manifest(account_path);
+    let created = manifest
+        .games
+        .values()
+        .map(|entry| entry.created)
+        .max()
+        .unwrap_or_else(|| get_latest_modified_time(account_path));
+    let label_file = account_path.join(LEGACY_BACKUP_LABEL_FILE);
+    let label = fs::read_to_string(&label_file).ok();
+    let snapshot = match create_snapshot_dir(account_path, created, label.as_deref()) {
+        Ok(snapshot) => snapshot,
+        Err(e) => {
+            log::warn!("Could not migrate {}: {}", account_path.display(), e);
+            return;
+        }
+    };
+    let _ = fs::remove_file(&label_file);
+    let manifest_file = account_path.join(BACKUP_MANIFEST_FILE);
+    for path in legacy_games
+        .iter()
+        .chain(std::iter::once(&manifest_file))
+        .filter(|p| p.exists())
+    {
+        if let Some(name) = path.file_name() {
+            if let Err(e) = fs::rename(path, snapshot.join(name)) {
+                log::warn!("Could not migrate {}: {}", path.display(), e);
+            }
+        }
+    }
+}
+
+/// Removes the snapshots nothing was backed up into; `remove_dir` leaves the others alone
+fn remove_empty_snapshots<'a>(snapshots: impl IntoIterator<Item = &'a PathBuf>) {
+    for snapshot in snapshots {
+        let _ = fs::remove_dir(snapshot);
+    }
+}
+
+fn read_account_snapshots(account_path: &Path) -> Vec<BackupSnapshot> {
+    match fs::read_dir(account_path) {
+        Ok(entries) => entries
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.is_dir())
+            .filter_map(|p| BackupSnapshot::from_path(&p))
+            .collect(),
+        Err(_) => vec![],
+    }
+}
+
+/// Every snapshot of one account, newest first
+fn list_account_snapshots(account_path: &Path) -> Vec<BackupSnapshot> {
+    migrate_legacy_backup(account_path);
+    let mut snapshots = read_account_snapshots(account_path);
+    snapshots.sort_by_key(|snapshot| std::cmp::Reverse(snapshot.created));
+    snapshots
+}
+
+/// The snapshot of an account taken at the time in `name`. Relabeling renames a snapshot, so
+/// names recorded earlier are matched by their time only.
+fn find_snapshot(account_path: &Path, name: &str) -> Option<BackupSnapshot> {
+    let (created, _) = parse_snapshot_name(name)?;
+    list_account_snapshots(account_path)
+        .into_iter()
+        .find(|snapshot| snapshot.created == created)
+}
+
+/// Every snapshot of every account in the backups folder
+fn list_backup_snapshots(userdata_path: &Path) -> Vec<BackupSnapshot> {
+    list_backup_dirs(userdata_path)
+        .iter()
+        .filter(|path| numeric_folder_name(path).is_some())
+        .flat_map(|path| list_account_snapshots(path))
+        .collect()
+}
+
+/// The newest snapshot of `account_id` holding a backup of `game_id`
+fn latest_game_backup(ud: &Path, account_id: &str, game_id: &str) -> Option<BackupSnapshot> {
+    list_account_snapshots(&backups_dir(ud).join(account_id))
+        .into_iter()
+        .find(|snapshot| snapshot.path.join(game_id).is_dir())
+}
+
+/// The snapshot holding the backup of `game_id` that `backup_id` refers to: the snapshot itself
+/// for a backup id, the newest backup of the game for a bare account id
+fn find_game_backup(ud: &Path, backup_id: &str, game_id: &str) -> Result<PathBuf, String> {
+    sanitize_id(game_id)?;
+    let snapshot = if backup_id.contains('/') {
+        sanitize_backup_id(backup_id)?;
+        profile_base_path(ud, backup_id, true)
+    } else {
+        let account_id = sanitize_id(backup_id)?;
+        latest_game_backup(ud, account_id, game_id)
+            .ok_or("Backup not found")?
+            .path
+    };
+    if !snapshot.join(game_id).is_dir() {
+        return Err("Backup not found".to_string());
+    }
+    Ok(snapshot)
+}
+
+// ─── Swap journal ───────────────────────────────────────────────────
+
+/// Lives in the backups folder, next to the account folders
+const SWAP_JOURNAL_FILE: &str = "swap-journal.json";
+const MAX_JOURNAL_ENTRIES: usize = 50;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwapJournalEntry {
+    /// Milliseconds since the epoch at which the swap finished, unique per swap
+    pub id: String,
+    /// Unix timestamp (seconds) of when the swap finished
+    pub created: u64,
+    /// The source folder; for a batch, every plan's source separated by `; `
+    pub source: String,
+    pub targets: Vec<JournalTarget>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalTarget {
+    pub target_id: String,
+    /// Name of the snapshot the swap backed the target up into, see `find_snapshot`. Missing
+    /// for swaps from before snapshots, which backed up into a single slot per account.
+    #[serde(default)]
+    pub backup: Option<String>,
+    pub games: Vec<JournalGame>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalGame {
+    pub game_id: String,
+    /// Whether the target had data that went into its backup. Undoing a game the target didn't
+    /// have deletes it again.
+    pub had_data: bool,
+    /// Set when only these files were swapped (`SwapOptions::files`); the backup then holds
+    /// just those files, so undoing restores them one by one instead of the whole folder
+    #[serde(default)]
+    pub files: Option<Vec<JournalFile>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalFile {
+    /// Relative to the game folder
+    pub path: String,
+    /// Whether the target had the file, which then went into the backup
+    pub had_data: bool,
+}
+
+/// An entry stamped with the current time, so backups taken during the swap count as older
+fn new_journal_entry(source: String, targets: Vec<JournalTarget>) -> SwapJournalEntry {
+    let now = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default();
+    SwapJournalEntry {
+        id: now.as_millis().to_string(),
+        created: now.as_secs(),
+        source,
+        targets,
+    }
+}
+
+fn read_swap_journal(ud: &Path) -> Vec<SwapJournalEntry> {
+    fs::read_to_string(backups_dir(ud).join(SWAP_JOURNAL_FILE))
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+/// Appends the entry, dropping the oldest ones past `MAX_JOURNAL_ENTRIES`
+fn append_swap_journal(ud: &Path, entry: SwapJournalEntry) -> Result<(), String> {
+    let mut journal = read_swap_journal(ud);
+    journal.push(entry);
+    let excess = journal.len().saturating_sub(MAX_JOURNAL_ENTRIES);
+    journal.drain(..excess);
+
+    let json = serde_json::to_string_pretty(&journal).map_err(|e| e.to_string())?;
+    fs::write(backups_dir(ud).join(SWAP_JOURNAL_FILE), json)
+        .map_err(|e| format!("Failed to write swap journal: {}", e))
+}
+
+/// Past swaps, newest first
+#[tauri::command]
+fn get_swap_journal(userdata_path: String) -> Vec<SwapJournalEntry> {
+    let mut journal = read_swap_journal(Path::new(&userdata_path));
+    journal.reverse();
+    journal
+}
+
+/// Rolls back every target of a journal entry: games the target had are restored from the
+/// snapshot the swap backed them up into, games it didn't have are deleted. Games whose backup
+/// was deleted since are reported as errors instead.
+#[tauri::command]
+fn undo_swap(userdata_path: String, journal_entry_id: String) -> SwapResult {
+    let ud = PathBuf::from(&userdata_path);
+    let Some(entry) = read_swap_journal(&ud)
+        .into_iter()
+        .find(|e| e.id == journal_entry_id)
+    else {
+        return SwapResult::failed("Swap not found in the journal");
+    };
+    let Some(_guard) = SwapGuard::acquire() else {
+        return SwapResult::failed(SWAP_BUSY_MESSAGE);
+    };
+    undo_journal_entry(&ud, &entry)
+}
+
+fn undo_journal_entry(ud: &Path, entry: &SwapJournalEntry) -> SwapResult {
+    let started = Instant::now();
+    let backups = backups_dir(ud);
+    let copy_options = CopyOptions::default();
+    let mut stats = CopyStats::default();
+    let mut details = Vec::new();
+    let mut games_swapped = 0;
+    let mut targets_affected = 0;
+    let mut target_results = Vec::new();
+
+    for target in &entry.targets {
+        let account_path = backups.join(&target.target_id);
+        let snapshot = target
+            .backup
+            .as_deref()
+            .and_then(|name| find_snapshot(&account_path, name));
+        let legacy_snapshots = match target.backup {
+            Some(_) => vec![],
+            None => list_account_snapshots(&account_path),
+        };
+        let restored_before = games_swapped;
+        let mut statuses = Vec::new();
+
+        for game in &target.games {
+            let target_game = ud.join(&target.target_id).join(&game.game_id);
+            let backup_game = match &target.backup {
+                Some(_) => snapshot
+                    .as_ref()
+                    .map(|snapshot| snapshot.path.join(&game.game_id))
+                    .unwrap_or_default(),
+                // The old single slot was migrated into a snapshot; its backup of the game is
+                // only this swap's if it was taken before the swap finished
+                None => legacy_snapshots
+                    .iter()
+                    .find(|snapshot| {
+                        read_backup_manifest(&snapshot.path)
+                            .games
+                            .get(&game.game_id)
+                            .is_some_and(|m| m.created <= entry.created)
+                    })
+                    .map(|snapshot| snapshot.path.join(&game.game_id))
+                    .unwrap_or_default(),
+            };
+            let needs_backup = match &game.files {
+                Some(files) => game.had_data && files.iter().any(|f| f.had_data),
+                None => game.had_data,
+            };
+            let outcome = if needs_backup && !backup_game.is_dir() {
+                Err("its backup no longer exists".to_string())
+            } else {
+                revert_journal_game(&backup_game, &target_game, game, &copy_options, &mut stats)
+            };
+
+            match outcome {
+                Ok(what) => {
+                    games_swapped += 1;
+                    details.push(format!(
+                        "Game {} for profile {} {}",
+                        game.game_id, target.target_id, what
+                    ));
+                    statuses.push(GameSwapStatus {
+                        game_id: game.game_id.clone(),
+                        status: SwapStatus::Swapped,
+                    });
+                }
+                Err(e) => {
+                    details.push(format!(
+                        "Error: Can't undo game {} for profile {}: {}",
+                        game.game_id, target.target_id, e
+                    ));
+                    statuses.push(GameSwapStatus {
+                        game_id: game.game_id.clone(),
+                        status: SwapStatus::Failed(e),
+                    });
+                }
+            }
+        }
+
+        if games_swapped > restored_before {
+            targets_affected += 1;
+        }
+        target_results.push(TargetResult {
+            target_id: target.target_id.clone(),
+            target_name: get_persona_name(ud, &target.target_id),
+            games: statuses,
+        });
+    }
+
+    let all_success = !details.iter().any(|d| d.starts_with("Error:"));
+    SwapResult {
+        success: all_success,
+        message: if all_success {
+            "Swap undone successfully!".to_string()
+        } else {
+            "Some games couldn't be restored. Check details.".to_string()
+        },
+        details,
+        games_swapped,
+        targets_affected,
+        bytes_copied: stats.bytes_copied,
+        files_copied: stats.files_copied + stats.files_reflinked,
+        elapsed_ms: started.elapsed().as_millis() as u64,
+        bytes_written: stats.bytes_copied,
+        targets: target_results,
+    }
+}
+
+/// Puts a swapped target game back the way the journal says it was: a whole folder from its
+/// backup, only the swapped files for a per-file swap, or nothing at all when the target didn't
+/// have the game
+fn revert_journal_game(
+    backup_game: &Path,
+    target_game: &Path,
+    game: &JournalGame,
+    options: &CopyOptions,
+    stats: &mut CopyStats,
+) -> Result<&'static str, String> {
+    match &game.files {
+        Some(files) if game.had_data => {
+            for file in files {
+                let target_file = target_game.join(&file.path);
+                if file.had_data {
+                    replace_file(&backup_game.join(&file.path), &target_file, options, stats)?;
+                } else if target_file.exists() {
+                    retry_io(&mut stats.retries, || fs::remove_file(&target_file))
+                        .map_err(|e| format!("Failed to remove {:?}: {}", target_file, e))?;
+                }
+            }
+            Ok("swapped files restored from backup")
+        }
+        None if game.had_data => restore_game_from_backup(backup_game, target_game, options, stats)
+            .map(|_| "restored from backup"),
+        _ if target_game.exists() => {
+            retry_io(&mut stats.retries, || fs::remove_dir_all(target_game))
+                .map(|_| "removed, the target had no data before")
+                .map_err(|e| e.to_string())
+        }
+        _ => Ok("already absent"),
+    }
+}
+
+/// Replaces the target game folder with a copy of the backup, leaving the backup in place
+fn restore_game_from_backup(
+    backup_game: &Path,
+    target_game: &Path,
+    options: &CopyOptions,
+    stats: &mut CopyStats,
+) -> Result<(), String> {
+    if target_game.exists() {
+        retry_io(&mut stats.retries, || fs::remove_dir_all(target_game))
+            .map_err(|e| format!("Failed to clear {:?}: {}", target_game, e))?;
+    }
+    copy_dir_recursive(backup_game, target_game, options, stats)
+}
+
+/// Whether two existing paths live on the same volume, i.e. a rename between them is a cheap
+/// metadata operation rather than a copy
+#[cfg(unix)]
+fn same_volume(a: &Path, b: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    match (fs::metadata(a), fs::metadata(b)) {
+        (Ok(a), Ok(b)) => a.dev() == b.dev(),
+        _ => false,
+    }
+}
+
+/// Compares drive letters / UNC shares. Volumes mounted into folders aren't detected, but a
+/// rename across them fails cleanly and the caller falls back to copying.
+#[cfg(windows)]
+fn same_volume(a: &Path, b: &Path) -> bool {
+    use std::path::Component;
+    let prefix = |p: &Path| {
+        p.canonicalize()
+            .ok()
+            .and_then(|c| match c.components().next() {
+                Some(Component::Prefix(prefix)) => {
+                    Some(prefix.as_os_str().to_string_lossy().to_lowercase())
+                }
+                _ => None,
+            })
+    };
+    matches!((prefix(a), prefix(b)), (Some(a), Some(b)) if a == b)
+}
+
+#[cfg(not(any(unix, windows)))]
+fn same_volume(_a: &Path, _b: &Path) -> bool {
+    false
+}
+
+/// Mount points for `drive_label`, listed once per command since refreshing them is slow
+struct Drives(Vec<PathBuf>);
+
+impl Drives {
+    #[cfg(unix)]
+    fn list() -> Drives {
+        let disks = sysinfo::Disks::new_with_refreshed_list();
+        Drives(
+            disks
+                .iter()
+                .map(|d| d.mount_point().to_path_buf())
+                .collect(),
+        )
+    }
+
+    /// Other platforms tell drives apart by the path alone
+    #[cfg(not(unix))]
+    fn list() -> Drives {
+        Drives(Vec::new())
+    }
+}
+
+/// The mount point holding `path` (the deepest one, for volumes mounted inside others)
+#[cfg(unix)]
+fn drive_label(path: &Path, drives: &Drives) -> String {
+    let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    drives
+        .0
+        .iter()
+        .filter(|mount| path.starts_with(mount))
+        .max_by_key(|mount| mount.components().count())
+        .map(|mount| normalize_path(mount))
+        .unwrap_or_default()
+}
+
+/// The drive letter (`C:`) or UNC share of `path`
+#[cfg(windows)]
+fn drive_label(path: &Path, _drives: &Drives) -> String {
+    use std::path::{Component, Prefix};
+    let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    match path.components().next() {
+        Some(Component::Prefix(prefix)) => match prefix.kind() {
+            Prefix::Disk(letter) | Prefix::VerbatimDisk(letter) => {
+                format!("{}:", (letter as char).to_ascii_uppercase())
+            }
+            Prefix::UNC(server, share) | Prefix::VerbatimUNC(server, share) => format!(
+                "\\\\{}\\{}",
+                server.to_string_lossy(),
+                share.to_string_lossy()
+            ),
+            _ => prefix.as_os_str().to_string_lossy().to_string(),
+        },
+        _ => String::new(),
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+fn drive_label(_path: &Path, _drives: &Drives) -> String {
+    String::new()
+}
+
+/// Where a backup went, for the swap details: `dunabackups/<profile_id>/<snapshot>`
+fn describe_snapshot(snapshot: &Path, profile_id: &str) -> String {
+    let name = snapshot.file_name().unwrap_or_default().to_string_lossy();
+    format!("{}/{}/{}", backups_dir_name(), profile_id, name)
+}
+
+/// Moves `game_path` into `<snapshot>/<game_id>` with a rename, which leaves the target
+/// cleared as well. Returns false without touching `game_path` if the rename fails, so the
+/// caller can fall back to `backup_game_folder`.
+fn move_game_to_backup(
+    backup_path: &Path,
+    game_path: &Path,
+    profile_id: &str,
+    game_id: &str,
+    stats: &mut CopyStats,
+    details: &mut Vec<String>,
+) -> bool {
+    let backup_game = backup_path.join(game_id);
+    if backup_game.exists()
+        && retry_io(&mut stats.retries, || fs::remove_dir_all(&backup_game)).is_err()
+    {
+        return false;
+    }
+    if fs::create_dir_all(backup_path).is_err()
+        || retry_io(&mut stats.retries, || fs::rename(game_path, &backup_game)).is_err()
+    {
+        return false;
+    }
+
+    details.push(format!(
+        "Backed up game {} for profile {} to {}",
+        game_id,
+        profile_id,
+        describe_snapshot(backup_path, profile_id)
+    ));
+    if let Err(e) = record_backup_in_manifest(backup_path, game_id) {
+        details.push(format!("Warning: {} ({}/{})", e, profile_id, game_id));
+    }
+    true
+}
+
+/// Fills `<snapshot>/<game_id>` with hard links to every file in `game_path`. Only safe when
+/// the swap deletes the target's files afterwards instead of writing into them. Returns false
+/// without leaving a partial backup if any link fails, so the caller can fall back to
+/// `backup_game_folder`.
+fn link_game_to_backup(
+    backup_path: &Path,
+    game_path: &Path,
+    profile_id: &str,
+    game_id: &str,
+    stats: &mut CopyStats,
+    details: &mut Vec<String>,
+) -> bool {
+    let backup_game = backup_path.join(game_id);
+    if backup_game.exists()
+        && retry_io(&mut stats.retries, || fs::remove_dir_all(&backup_game)).is_err()
+    {
+        return false;
+    }
+
+    let linked = WalkDir::new(game_path)
+        .into_iter()
+        .try_for_each(|entry| -> io::Result<()> {
+            let entry = entry?;
+            let rel = entry
+                .path()
+                .strip_prefix(game_path)
+                .map_err(io::Error::other)?;
+            let dst = backup_game.join(rel);
+            if entry.file_type().is_dir() {
+                fs::create_dir_all(&dst)
+            } else {
+                fs::hard_link(entry.path(), &dst)
+            }
+        });
+    if let Err(e) = linked {
+        let _ = fs::remove_dir_all(&backup_game);
+        details.push(format!(
+            "Warning: Hard-linking backup for {}/{} failed, copying instead: {}",
+            profile_id, game_id, e
+        ));
+        return false;
+    }
+
+    details.push(format!(
+        "Backed up game {} for profile {} to {} (hard links)",
+        game_id,
+        profile_id,
+        describe_snapshot(backup_path, profile_id)
+    ));
+    if let Err(e) = record_backup_in_manifest(backup_path, game_id) {
+        details.push(format!("Warning: {} ({}/{})", e, profile_id, game_id));
+    }
+    true
+}
+
+/// Gives each file of `backup_game` that is still hard-linked to a file left in `target_game`
+/// its own copy, so writing to the target afterwards can't change the backup. Returns how many
+/// files were unlinked.
+fn unlink_backup_from_target(backup_game: &Path, target_game: &Path) -> io::Result<usize> {
+    let mut unlinked = 0;
+    for entry in WalkDir::new(target_game) {
+        let entry = entry?;
+        if entry.file_type().is_dir() {
+            continue;
+        }
+        let rel = entry
+            .path()
+            .strip_prefix(target_game)
+            .map_err(io::Error::other)?;
+        let backup_file = backup_game.join(rel);
+        if !same_file(entry.path(), &backup_file) {
+            continue;
+        }
+        let mut tmp_name = backup_file.file_name().unwrap_or_default().to_os_string();
+        tmp_name.push(".unlinking");
+        let tmp = backup_file.with_file_name(tmp_name);
+        fs::copy(&backup_file, &tmp)?;
+        fs::rename(&tmp, &backup_file)?;
+        unlinked += 1;
+    }
+    Ok(unlinked)
+}
+
+/// Copies `game_path` into `<snapshot>/<game_id>`, replacing what a failed move or link left
+/// there. Progress is reported through `details`; returns false when the backup could not be
+/// taken.
+fn backup_game_folder(
+    backup_path: &Path,
+    game_path: &Path,
+    profile_id: &str,
+    game_id: &str,
+    options: &CopyOptions,
+    stats: &mut CopyStats,
+    details: &mut Vec<String>,
+) -> bool {
+    let backup_game = backup_path.join(game_id);
+    if backup_game.exists() {
+        if let Err(e) = retry_io(&mut stats.retries, || fs::remove_dir_all(&backup_game)) {
+            details.push(format!(
+                "Warning: Failed to remove old backup for {}/{}: {}",
+                profile_id, game_id, e
+            ));
+        }
+    }
+
+    if let Err(e) = fs::create_dir_all(&backup_game) {
+        details.push(format!(
+            "Warning: Failed to create backup dir for {}/{}: {}",
+            profile_id, game_id, e
+        ));
+        return false;
+    }
+
+    let _cleanup = UnwindCleanup(&backup_game);
+    if let Err(e) = copy_dir_recursive(game_path, &backup_game, options, stats) {
+        details.push(format!(
+            "Warning: Backup failed for {}/{}: {}",
+            profile_id, game_id, e
+        ));
+        return false;
+    }
+
+    details.push(format!(
+        "Backed up game {} for profile {} to {}",
+        game_id,
+        profile_id,
+        describe_snapshot(backup_path, profile_id)
+    ));
+
+    // The copy itself succeeded, a missing manifest only degrades later verification
+    if let Err(e) = record_backup_in_manifest(backup_path, game_id) {
+        details.push(format!("Warning: {} ({}/{})", e, profile_id, game_id));
+    }
+
+    true
+}
+
+#[tauri::command]
+fn cloud_risk_report(
+    userdata_path: String,
+    steam_path: String,
+    target_ids: Vec<String>,
+    game_ids: Vec<String>,
+) -> Result<Vec<CloudRisk>, String> {
+    sanitize_ids(target_ids.iter().chain(&game_ids))?;
+
+    let ud = PathBuf::from(&userdata_path);
+    let steam = Path::new(&steam_path);
+    let steamapps_dirs = find_all_steamapps_dirs(steam);
+    let appinfo_games = get_appinfo_games(steam);
+
+    let mut report = Vec::new();
+    for target_id in &target_ids {
+        let target_name = get_persona_name(&ud, target_id);
+        for game_id in &game_ids {
+            let game_name = get_game_info(&appinfo_games, &steamapps_dirs, game_id)
+                .map(|(name, _)| name)
+                .unwrap_or_else(|| game_id.clone());
+            report.push(CloudRisk {
+                target_id: target_id.clone(),
+                target_name: target_name.clone(),
+                game_id: game_id.clone(),
+                game_name,
+                cloud_enabled: has_remotecache(&ud.join(target_id).join(game_id)),
+            });
+        }
+    }
+
+    Ok(report)
+}
+
+/// How many of a profile's games sync with Steam Cloud, for a warning before swapping the
+/// whole profile
+#[tauri::command]
+fn profile_cloud_summary(
+    userdata_path: String,
+    steam_path: String,
+    profile_id: String,
+    is_backup: bool,
+) -> Result<CloudSummary, String> {
+    sanitize_profile_id(&profile_id, is_backup)?;
+    let profile_path = profile_base_path(Path::new(&userdata_path), &profile_id, is_backup);
+    if !profile_path.is_dir() {
+        return Err("Profile not found".to_string());
+    }
+    let steam = Path::new(&steam_path);
+    let steamapps_dirs = find_all_steamapps_dirs(steam);
+    let appinfo_games = get_appinfo_games(steam);
+
+    let game_ids = list_game_folder_ids(&profile_path);
+    let mut cloud_game_names: Vec<String> = game_ids
+        .iter()
+        .filter(|game_id| has_remotecache(&profile_path.join(game_id)))
+        .map(|game_id| {
+            get_game_info(&appinfo_games, &steamapps_dirs, game_id)
+                .map(|(name, _)| name)
+                .unwrap_or_else(|| game_id.clone())
+        })
+        .collect();
+    cloud_game_names.sort_by_key(|name| name.to_lowercase());
+
+    Ok(CloudSummary {
+        profile_id,
+        total_games: game_ids.len(),
+        cloud_games: cloud_game_names.len(),
+        local_games: game_ids.len() - cloud_game_names.len(),
+        cloud_game_names,
+    })
+}
+
+/// Everything the confirmation screen needs to warn about for one target in a single call
+#[tauri::command]
+fn target_safety(
+    userdata_path: String,
+    steam_path: String,
+    target_id: String,
+    game_ids: Vec<String>,
+) -> Result<TargetSafety, String> {
+    sanitize_ids(std::iter::once(&target_id).chain(&game_ids))?;
+    let ud = PathBuf::from(&userdata_path);
+    let steam = Path::new(&steam_path);
+    let steamapps_dirs = find_all_steamapps_dirs(steam);
+    let appinfo_games = get_appinfo_games(steam);
+
+    let target_base = ud.join(&target_id);
+    let snapshots = list_account_snapshots(&backups_dir(&ud).join(&target_id));
+    let running = running_process_names();
+
+    let games = game_ids
+        .iter()
+        .map(|game_id| {
+            let target_game = target_base.join(game_id);
+            let has_data = has_meaningful_game_data(&target_game);
+            // Snapshots are newest first
+            let backup = snapshots.iter().find(|s| s.path.join(game_id).is_dir());
+            let has_backup = backup.is_some();
+
+            // Prefer the manifest's timestamp over the snapshot's, it says when the game itself
+            // was backed up
+            let backup_time = backup.map(|snapshot| {
+                let created = read_backup_manifest(&snapshot.path)
+                    .games
+                    .get(game_id)
+                    .map_or(snapshot.created, |entry| entry.created);
+                SystemTime::UNIX_EPOCH + Duration::from_secs(created)
+            });
+            let data_time = get_dir_stats(&target_game).3;
+            let newer_than_backup = has_data
+                && match (data_time, backup_time) {
+                    (Some(data), Some(backup)) => data > backup,
+                    _ => true,
+                };
+
+            let (game_name, executables) = get_game_info(&appinfo_games, &steamapps_dirs, game_id)
+                .unwrap_or_else(|| (game_id.clone(), vec![]));
+
+            GameSafety {
+                game_id: game_id.clone(),
+                game_name,
+                has_data,
+                has_backup,
+                newer_than_backup,
+                cloud_enabled: has_remotecache(&target_game),
+                running: executables
+                    .iter()
+                    .any(|exe| running.contains(&exe.to_lowercase())),
+            }
+        })
+        .collect();
+
+    Ok(TargetSafety {
+        target_name: get_persona_name(&ud, &target_id),
+        target_id,
+        games,
+    })
+}
+
+/// Game folders with data whose appid resolves to no name, typically games the account no
+/// longer owns or that were removed from the store
+#[tauri::command]
+fn orphaned_games(userdata_path: String, steam_path: String) -> Vec<OrphanedGame> {
+    let ud = PathBuf::from(&userdata_path);
+    let steam = Path::new(&steam_path);
+    let steamapps_dirs = find_all_steamapps_dirs(steam);
+    let appinfo_games = get_appinfo_games(steam);
+
+    let mut orphans = Vec::new();
+    for profile in discover_profiles(&ud, steam, &steamapps_dirs) {
+        if profile.is_backup {
+            continue;
+        }
+        let profile_path = ud.join(&profile.id);
+        for game_id in list_game_folder_ids(&profile_path) {
+            if get_game_info(&appinfo_games, &steamapps_dirs, &game_id).is_some() {
+                continue;
+            }
+            orphans.push(OrphanedGame {
+                profile_id: profile.id.clone(),
+                profile_name: profile.name.clone(),
+                size_bytes: dir_size_only(&profile_path.join(&game_id)),
+                game_id,
+            });
+        }
+    }
+
+    orphans
+}
+
+/// Deletes orphaned game folders from a profile, backing each one up to dunabackups first.
+/// Folders that turn out to belong to a known game are left alone.
+#[tauri::command]
+fn delete_orphaned_games(
+    userdata_path: String,
+    steam_path: String,
+    profile_id: String,
+    game_ids: Vec<String>,
+) -> SwapResult {
+    if let Err(e) = sanitize_ids(std::iter::once(&profile_id).chain(&game_ids)) {
+        return SwapResult::failed(e);
+    }
+    let Some(_guard) = SwapGuard::acquire() else {
+        return SwapResult::failed(SWAP_BUSY_MESSAGE);
+    };
+
+    let ud = PathBuf::from(&userdata_path);
+    let steam = Path::new(&steam_path);
+    let steamapps_dirs = find_all_steamapps_dirs(steam);
+    let appinfo_games = get_appinfo_games(steam);
+
+    let snapshot = match create_backup_snapshot(&backups_dir(&ud), &profile_id, None) {
+        Ok(snapshot) => snapshot,
+        Err(e) => return SwapResult::failed(e),
+    };
+
+    let copy_options = CopyOptions::default();
+    let mut stats = CopyStats::default();
+    let mut details = Vec::new();
+
+    for game_id in &game_ids {
+        let game_path = ud.join(&profile_id).join(game_id);
+        if !game_path.is_dir() {
+            details.push(format!("Warning: Game {} not found, skipped", game_id));
+            continue;
+        }
+        if get_game_info(&appinfo_games, &steamapps_dirs, game_id).is_some() {
+            details.push(format!(
+                "Warning: Game {} is not orphaned, skipped",
+                game_id
+            ));
+            continue;
+        }
+
+        // Never delete without a backup to fall back on
+        if !backup_game_folder(
+            &snapshot,
+            &game_path,
+            &profile_id,
+            game_id,
+            &copy_options,
+            &mut stats,
+            &mut details,
+        ) {
+            details.push(format!(
+                "Error: Game {} was not deleted because the backup failed",
+                game_id
+            ));
+            continue;
+        }
+
+        match retry_io(&mut stats.retries, || fs::remove_dir_all(&game_path)) {
+            Ok(_) => details.push(format!("Deleted orphaned game {}", game_id)),
+            Err(e) => details.push(format!("Error: Failed to delete game {}: {}", game_id, e)),
+        }
+    }
+    remove_empty_snapshots([&snapshot]);
+
+    let all_success = !details.iter().any(|d| d.starts_with("Error:"));
+
+    SwapResult {
+        success: all_success,
+        message: if all_success {
+            "Orphaned games deleted".to_string()
+        } else {
+            "Some deletions failed. Check details.".to_string()
+        },
+        details,
+        ..Default::default()
+    }
+}
+
+/// Deletes a backup snapshot (`<account id>/<snapshot>`) or one game of it. A bare account id
+/// deletes every snapshot of the account, or with a game id that game's backup in each of them.
+#[tauri::command]
+fn delete_backup(
+    userdata_path: String,
+    target_id: String,
+    game_id: Option<String>,
+) -> DeleteBackupResult {
+    let is_snapshot = target_id.contains('/');
+    let valid = if is_snapshot {
+        sanitize_backup_id(&target_id).map(|_| ())
+    } else {
+        sanitize_id(&target_id).map(|_| ())
+    };
+    if let Err(e) = valid.and_then(|_| sanitize_ids(&game_id)) {
+        return DeleteBackupResult {
+            success: false,
+            message: e,
+            bytes_freed: 0,
+        };
+    }
+    let Some(_guard) = SwapGuard::acquire() else {
+        return DeleteBackupResult {
+            success: false,
+            message: SWAP_BUSY_MESSAGE.to_string(),
+            bytes_freed: 0,
+        };
+    };
+
+    let ud = Path::new(&userdata_path);
+    let backups_dir = backups_dir(ud);
+    let backup_path = profile_base_path(ud, &target_id, true);
+    let delete_paths: Vec<PathBuf> = match game_id {
+        None => vec![backup_path],
+        Some(ref gid) if is_snapshot => vec![backup_path.join(gid)],
+        Some(ref gid) => list_account_snapshots(&backup_path)
+            .iter()
+            .map(|snapshot| snapshot.path.join(gid))
+            .collect(),
+    };
+    let delete_paths: Vec<PathBuf> = delete_paths.into_iter().filter(|p| p.exists()).collect();
+
+    if delete_paths.is_empty() {
+        return DeleteBackupResult {
+            success: false,
+            message: "Backup not found".to_string(),
+            bytes_freed: 0,
+        };
+    }
+
+    // Refuse anything that resolves outside dunabackups (e.g. ids containing "..") or to
+    // dunabackups itself
+    let inside_backups = delete_paths.iter().all(|delete_path| {
+        match (backups_dir.canonicalize(), delete_path.canonicalize()) {
+            (Ok(base), Ok(target)) => target.starts_with(&base) && target != base,
+            _ => false,
+        }
+    });
+    if !inside_backups {
+        return DeleteBackupResult {
+            success: false,
+            message: "Refusing to delete a path outside the backups folder".to_string(),
+            bytes_freed: 0,
+        };
+    }
+
+    let mut bytes_freed = 0;
+    for delete_path in &delete_paths {
+        let size = dir_size_only(delete_path);
+        if let Err(e) = fs::remove_dir_all(delete_path) {
+            return DeleteBackupResult {
+                success: false,
+                message: format!("Failed to delete backup: {}", e),
+                bytes_freed,
+            };
+        }
+        bytes_freed += size;
+
+        // Keep the snapshot's manifest in sync when only a single game was removed
+        if let (Some(gid), Some(snapshot)) = (&game_id, delete_path.parent()) {
+            let mut manifest = read_backup_manifest(snapshot);
+            if manifest.games.remove(gid).is_some() {
+                let _ = write_backup_manifest(snapshot, &manifest);
+            }
+        }
+    }
+
+    DeleteBackupResult {
+        success: true,
+        message: "Backup deleted".to_string(),
+        bytes_freed,
+    }
+}
+
+#[tauri::command]
+fn verify_backup(
+    userdata_path: String,
+    target_id: String,
+    game_id: String,
+) -> Result<VerifyReport, String> {
+    let backup_path = find_game_backup(Path::new(&userdata_path), &target_id, &game_id)?;
+    let game_path = backup_path.join(&game_id);
+
+    let (actual_size, actual_file_count, _, _) = get_dir_stats(&game_path);
+    let manifest = read_backup_manifest(&backup_path);
+    let Some(entry) = manifest.games.get(&game_id) else {
+        return Ok(VerifyReport {
+            ok: false,
+            has_manifest: false,
+            expected_file_count: 0,
+            actual_file_count,
+            expected_size: 0,
+            actual_size,
+            mismatches: vec!["No manifest entry for this backup, it can't be verified".to_string()],
+        });
+    };
+
+    // The manifest only records totals; there are no per-file hashes to re-check
+    let mut mismatches = Vec::new();
+    if entry.file_count != actual_file_count {
+        mismatches.push(format!(
+            "File count differs: expected {}, found {}",
+            entry.file_count, actual_file_count
+        ));
+    }
+    if entry.total_size != actual_size {
+        mismatches.push(format!(
+            "Total size differs: expected {} bytes, found {} bytes",
+            entry.total_size, actual_size
+        ));
+    }
+
+    Ok(VerifyReport {
+        ok: mismatches.is_empty(),
+        has_manifest: true,
+        expected_file_count: entry.file_count,
+        actual_file_count,
+        expected_size: entry.total_size,
+        actual_size,
+        mismatches,
+    })
+}
+
+/// Finds backups of the same game in different snapshots with identical contents (same
+/// `game_fingerprint`) and turns the copies into hard links to the newest one. Every snapshot
+/// stays restorable on its own, only the duplicate data is freed. remotecache.vdf files, which the
+/// fingerprint ignores, are left as they are.
+#[tauri::command]
+fn dedupe_backups(userdata_path: String) -> DedupeReport {
+    let ud = PathBuf::from(&userdata_path);
+    let mut report = DedupeReport::default();
+    let Some(_guard) = SwapGuard::acquire() else {
+        report.details.push(format!("Error: {}", SWAP_BUSY_MESSAGE));
+        return report;
+    };
+
+    // (game id, fingerprint) → backup game folders with their backup time
+    let mut groups: HashMap<(String, String), Vec<(PathBuf, u64)>> = HashMap::new();
+    for snapshot in list_backup_snapshots(&ud) {
+        let manifest = read_backup_manifest(&snapshot.path);
+        for game_id in list_game_folder_ids(&snapshot.path) {
+            let game_path = snapshot.path.join(&game_id);
+            let fingerprint = match fingerprint_folder(&game_path) {
+                Ok(f) => f,
+                Err(e) => {
+                    report.details.push(format!(
+                        "Warning: Skipped {}: {}",
+                        normalize_path(&game_path),
+                        e
+                    ));
+                    continue;
+                }
+            };
+            let created = manifest
+                .games
+                .get(&game_id)
+                .map_or(snapshot.created, |m| m.created);
+            groups
+                .entry((game_id, fingerprint))
+                .or_default()
+                .push((game_path, created));
+        }
+    }
+
+    for mut copies in groups.into_values().filter(|c| c.len() > 1) {
+        copies.sort_by_key(|(_, created)| std::cmp::Reverse(*created));
+        let (keep, _) = copies.remove(0);
+        for (duplicate, _) in copies {
+            report.duplicates_found += 1;
+            match link_duplicate_folder(&keep, &duplicate) {
+                Ok(bytes) => {
+                    report.duplicates_linked += 1;
+                    report.bytes_reclaimed += bytes;
+                }
+                Err(e) => report.details.push(format!(
+                    "Warning: Could not link {} to {}: {}",
+                    normalize_path(&duplicate),
+                    normalize_path(&keep),
+                    e
+                )),
+            }
+        }
+    }
+
+    report
+}
+
+/// Replaces every file of `duplicate` with a hard link to the same file in `keep`, returning
+/// the bytes that were freed. Links are created under a temporary name and renamed over the
+/// copy, so a failure leaves the copy in place.
+fn link_duplicate_folder(keep: &Path, duplicate: &Path) -> Result<u64, String> {
+    let mut freed = 0;
+    for (rel, metadata) in list_files_relative(duplicate) {
+        let dup_file = duplicate.join(&rel);
+        let keep_file = keep.join(&rel);
+        if is_remotecache(&dup_file) || same_file(&keep_file, &dup_file) {
+            continue;
+        }
+
+        let mut tmp_name = dup_file.file_name().unwrap_or_default().to_os_string();
+        tmp_name.push(".dedupe");
+        let tmp = dup_file.with_file_name(tmp_name);
+        fs::hard_link(&keep_file, &tmp).map_err(|e| e.to_string())?;
+        if let Err(e) = fs::rename(&tmp, &dup_file) {
+            let _ = fs::remove_file(&tmp);
+            return Err(e.to_string());
+        }
+        freed += metadata.len();
+    }
+    Ok(freed)
+}
+
+/// Whether both paths are hard links to the same file: device and inode on unix, volume
+/// serial number and file index on Windows
+fn same_file(a: &Path, b: &Path) -> bool {
+    same_file::is_same_file(a, b).unwrap_or(false)
+}
+
+const BACKUP_AGE_BUCKETS: [(&str, u64); 4] = [
+    ("Under a day", 24 * 60 * 60),
+    ("Under a week", 7 * 24 * 60 * 60),
+    ("Under a month", 30 * 24 * 60 * 60),
+    ("Under a year", 365 * 24 * 60 * 60),
+];
+
+/// How many game backups fall into each age range, youngest first, as (label, count, total
+/// bytes). A game's age comes from the backup manifest, or the snapshot's time for backups
+/// taken before manifests existed.
+#[tauri::command]
+fn backup_age_histogram(userdata_path: String) -> Vec<(String, usize, u64)> {
+    let ud = PathBuf::from(&userdata_path);
+    let now = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let mut buckets: Vec<(String, usize, u64)> = BACKUP_AGE_BUCKETS
+        .iter()
+        .map(|(label, _)| label)
+        .chain(std::iter::once(&"Older"))
+        .map(|label| (label.to_string(), 0, 0))
+        .collect();
+
+    for snapshot in list_backup_snapshots(&ud) {
+        let manifest = read_backup_manifest(&snapshot.path);
+        for game_id in list_game_folder_ids(&snapshot.path) {
+            let (created, size) = match manifest.games.get(&game_id) {
+                Some(entry) => (entry.created, entry.total_size),
+                None => (
+                    snapshot.created,
+                    dir_size_only(&snapshot.path.join(&game_id)),
+                ),
+            };
+            let age = now.saturating_sub(created);
+            let bucket = BACKUP_AGE_BUCKETS
+                .iter()
+                .position(|(_, max_age)| age < *max_age)
+                .unwrap_or(BACKUP_AGE_BUCKETS.len());
+            buckets[bucket].1 += 1;
+            buckets[bucket].2 += size;
+        }
+    }
+    buckets
+}
+
+/// Zips the backup of `game_id` in the snapshot `target_id` (the newest one for a bare account
+/// id) into `out_path`, returning the archive size. Entries are stored relative to the game
+/// folder.
+#[tauri::command]
+fn archive_backup(
+    userdata_path: String,
+    target_id: String,
+    game_id: String,
+    out_path: String,
+) -> Result<u64, String> {
+    let game_path =
+        find_game_backup(Path::new(&userdata_path), &target_id, &game_id)?.join(&game_id);
+
+    let out_path = PathBuf::from(&out_path);
+    let file = fs::File::create(&out_path).map_err(|e| {
+        format!(
+            "Failed to create archive {}: {}",
+            normalize_path(&out_path),
+            e
+        )
+    })?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+
+    for entry in WalkDir::new(&game_path).follow_links(true).min_depth(1) {
+        let entry = entry.map_err(|e| format!("Failed to read backup: {}", e))?;
+        let rel = entry
+            .path()
+            .strip_prefix(&game_path)
+            .map_err(|e| e.to_string())?;
+        // Zip entry names are UTF-8; refusing is better than an archive that restores wrong
+        let name = rel
+            .to_str()
+            .ok_or_else(|| format!("Can't archive file with a non UTF-8 name: {:?}", rel))?
+            .replace('\\', "/");
+
+        if entry.file_type().is_dir() {
+            zip.add_directory(name, options)
+                .map_err(|e| format!("Failed to write archive: {}", e))?;
+        } else {
+            zip.start_file(name, options)
+                .map_err(|e| format!("Failed to write archive: {}", e))?;
+            let mut src = fs::File::open(entry.path())
+                .map_err(|e| format!("Failed to read {}: {}", normalize_path(entry.path()), e))?;
+            io::copy(&mut src, &mut zip).map_err(|e| format!("Failed to write archive: {}", e))?;
+        }
+    }
+
+    zip.finish()
+        .map_err(|e| format!("Failed to write archive: {}", e))?;
+    fs::metadata(&out_path)
+        .map(|m| m.len())
+        .map_err(|e| e.to_string())
+}
+
+/// Unpacks an archive made by `archive_backup` into a new snapshot of `target_id`'s account,
+/// which only shows up once the whole archive extracted successfully. Returns the snapshot's
+/// backup id.
+#[tauri::command]
+fn restore_from_archive(
+    userdata_path: String,
+    target_id: String,
+    game_id: String,
+    archive_path: String,
+) -> Result<String, String> {
+    let account_id = backup_account_id(&target_id);
+    sanitize_id(account_id)?;
+    sanitize_id(&game_id)?;
+    let _guard = SwapGuard::acquire().ok_or(SWAP_BUSY_MESSAGE)?;
+    let backups_dir = backups_dir(Path::new(&userdata_path));
+    let staging = backups_dir
+        .join(account_id)
+        .join(format!("{}.restoring", game_id));
+
+    let file = fs::File::open(&archive_path)
+        .map_err(|e| format!("Failed to open archive {}: {}", archive_path, e))?;
+    let mut archive =
+        zip::ZipArchive::new(file).map_err(|e| format!("Not a valid archive: {}", e))?;
+
+    let _ = fs::remove_dir_all(&staging);
+    fs::create_dir_all(&staging).map_err(|e| format!("Failed to create backup dir: {}", e))?;
+    // extract() rejects entries that would land outside the staging folder
+    if let Err(e) = archive.extract(&staging) {
+        let _ = fs::remove_dir_all(&staging);
+        return Err(format!("Failed to extract archive: {}", e));
+    }
+
+    let snapshot = create_backup_snapshot(&backups_dir, account_id, None).inspect_err(|_| {
+        let _ = fs::remove_dir_all(&staging);
+    })?;
+    if let Err(e) = fs::rename(&staging, snapshot.join(&game_id)) {
+        let _ = fs::remove_dir_all(&staging);
+        remove_empty_snapshots([&snapshot]);
+        return Err(format!("Failed to restore backup: {}", e));
+    }
+
+    record_backup_in_manifest(&snapshot, &game_id)?;
+    BackupSnapshot::from_path(&snapshot)
+        .map(|snapshot| snapshot.id())
+        .ok_or_else(|| "Failed to restore backup".to_string())
+}
+
+#[tauri::command]
+fn backup_profile(
+    userdata_path: String,
+    profile_id: String,
+    game_ids: Vec<String>,
+    label: Option<String>,
+) -> SwapResult {
+    if let Err(e) = sanitize_ids(std::iter::once(&profile_id).chain(&game_ids)) {
+        return SwapResult::failed(e);
+    }
+    let Some(_guard) = SwapGuard::acquire() else {
+        return SwapResult::failed(SWAP_BUSY_MESSAGE);
+    };
+
+    let ud = PathBuf::from(&userdata_path);
+    let profile_path = ud.join(&profile_id);
+    if !profile_path.is_dir() {
+        return SwapResult::failed("Profile not found");
+    }
+
+    let snapshot = match create_backup_snapshot(&backups_dir(&ud), &profile_id, label.as_deref()) {
+        Ok(snapshot) => snapshot,
+        Err(e) => return SwapResult::failed(e),
+    };
+
+    let options = CopyOptions::default();
+    let mut stats = CopyStats::default();
+    let mut details = Vec::new();
+    let mut all_success = true;
+
+    for game_id in &game_ids {
+        let game_path = profile_path.join(game_id);
+        if !game_path.exists() {
+            details.push(format!(
+                "Warning: Profile {} has no data for game {} — skipped",
+                profile_id, game_id
+            ));
+            continue;
+        }
+
+        if !backup_game_folder(
+            &snapshot,
+            &game_path,
+            &profile_id,
+            game_id,
+            &options,
+            &mut stats,
+            &mut details,
+        ) {
+            all_success = false;
+        }
+    }
+
+    if stats.retries > 0 {
+        details.push(format!(
+            "Retried {} file operation(s) after transient failures (file in use)",
+            stats.retries
+        ));
+    }
+
+    remove_empty_snapshots([&snapshot]);
+
+    SwapResult {
+        success: all_success,
+        message: if all_success {
+            "Backup completed successfully!".to_string()
+        } else {
+            "Some backups failed. Check details.".to_string()
+        },
+        details,
+        ..Default::default()
+    }
+}
+
+// ─── Transient IO retries ───────────────────────────────────────────
+
+const RETRY_DELAYS_MS: [u64; 2] = [100, 400];
+
+fn is_transient_io_error(e: &io::Error) -> bool {
+    // Antivirus scanners and Steam briefly hold save files open on Windows, which surfaces as
+    // ERROR_SHARING_VIOLATION (32) / ERROR_LOCK_VIOLATION (33) or plain access denied.
+    // Elsewhere a permission error is real and retrying it only delays the failure.
+    if cfg!(windows)
+        && (matches!(e.raw_os_error(), Some(32) | Some(33))
+            || e.kind() == io::ErrorKind::PermissionDenied)
+    {
+        return true;
+    }
+    matches!(
+        e.kind(),
+        io::ErrorKind::ResourceBusy
+            | io::ErrorKind::Interrupted
+            | io::ErrorKind::WouldBlock
+            | io::ErrorKind::TimedOut
+    )
+}
+
+/// Runs `op`, retrying with increasing delays while it fails with a transient error
+fn retry_io<T>(retries: &mut usize, mut op: impl FnMut() -> io::Result<T>) -> io::Result<T> {
+    let mut attempt = 0;
+    loop {
+        match op() {
+            Err(e) if attempt < RETRY_DELAYS_MS.len() && is_transient_io_error(&e) => {
+                std::thread::sleep(Duration::from_millis(RETRY_DELAYS_MS[attempt]));
+                attempt += 1;
+                *retries += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct CopyOptions {
+    /// Try a copy-on-write clone first (Btrfs, XFS, APFS, ReFS). Filesystems without reflink
+    /// support fall back to a regular data copy per file.
+    reflink: bool,
+    /// Leave symlinks and junctions/reparse points out of the copy instead of copying what
+    /// they point to
+    skip_reparse_points: bool,
+    /// Sleep between files to keep the average data copy rate under this limit. Reflinked
+    /// files write no data and don't count towards it.
+    max_bytes_per_sec: Option<u64>,
+    /// Leave remotecache.vdf files out of the copy
+    skip_remotecache: bool,
+    /// Copy data through a buffered reader/writer of this size instead of `fs::copy`
+    buffer_size: Option<usize>,
+    /// Flush every copied file, and every folder once filled, to disk before moving on
+    durable: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+struct CopyStats {
+    files_copied: usize,
+    files_reflinked: usize,
+    bytes_copied: u64,
+    /// Start of the throttled copy and the data bytes written since
+    throttle: Option<(Instant, u64)>,
+    /// File operations that only succeeded after retrying a transient failure
+    retries: usize,
+    /// Links left out because `skip_reparse_points` was set
+    skipped_links: Vec<PathBuf>,
+    /// remotecache.vdf files left out because `skip_remotecache` was set
+    skipped_remotecaches: usize,
+}
+
+impl CopyStats {
+    /// Adds the counts of an operation that kept its own stats
+    fn absorb(&mut self, other: CopyStats) {
+        self.files_copied += other.files_copied;
+        self.files_reflinked += other.files_reflinked;
+        self.bytes_copied += other.bytes_copied;
+        self.retries += other.retries;
+        self.skipped_links.extend(other.skipped_links);
+        self.skipped_remotecaches += other.skipped_remotecaches;
+    }
+}
+
+fn copy_file(
+    src: &Path,
+    dst: &Path,
+    options: &CopyOptions,
+    stats: &mut CopyStats,
+) -> Result<(), String> {
+    if options.reflink {
+        // reflink_or_copy returns None when the file was cloned, Some(bytes) when it fell back
+        match retry_io(&mut stats.retries, || {
+            reflink_copy::reflink_or_copy(src, dst)
+        }) {
+            Ok(None) => {
+                stats.files_reflinked += 1;
+                stats.bytes_copied += fs::metadata(dst).map(|m| m.len()).unwrap_or(0);
+            }
+            Ok(Some(bytes)) => {
+                stats.files_copied += 1;
+                stats.bytes_copied += bytes;
+                throttle_copy(options, stats, bytes);
+            }
+            Err(e) => return Err(format!("Failed to copy {:?} -> {:?}: {}", src, dst, e)),
+        }
+        return sync_copied_file(dst, options);
+    }
+
+    let bytes = retry_io(&mut stats.retries, || match options.buffer_size {
+        Some(size) => buffered_copy(src, dst, size),
+        None => fs::copy(src, dst),
+    })
+    .map_err(|e| format!("Failed to copy {:?} -> {:?}: {}", src, dst, e))?;
+    stats.files_copied += 1;
+    stats.bytes_copied += bytes;
+    throttle_copy(options, stats, bytes);
+    sync_copied_file(dst, options)
+}
+
+/// With `durable`, waits until the file's data has reached the disk instead of the page cache
+fn sync_copied_file(dst: &Path, options: &CopyOptions) -> Result<(), String> {
+    if !options.durable {
+        return Ok(());
+    }
+    // Windows only flushes through a handle opened for writing
+    let file = if cfg!(windows) {
+        fs::OpenOptions::new().write(true).open(dst)
+    } else {
+        fs::File::open(dst)
+    };
+    file.and_then(|f| f.sync_all())
+        .map_err(|e| format!("Failed to flush {:?} to disk: {}", dst, e))
+}
+
+/// Flushes a folder's entries (the names of files and folders created in it) to disk. std can't
+/// open folders on Windows, where NTFS journals that metadata anyway, so elsewhere this is a no-op.
+#[cfg(unix)]
+fn sync_dir(dir: &Path) -> Result<(), String> {
+    fs::File::open(dir)
+        .and_then(|d| d.sync_all())
+        .map_err(|e| format!("Failed to flush {:?} to disk: {}", dir, e))
+}
+
+#[cfg(not(unix))]
+fn sync_dir(_dir: &Path) -> Result<(), String> {
+    Ok(())
+}
+
+/// Copies over an existing file by unlinking it first instead of writing into it. Backups may
+/// share files through hard links (`dedupe_backups`), which would otherwise change together.
+fn replace_file(
+    src: &Path,
+    dst: &Path,
+    options: &CopyOptions,
+    stats: &mut CopyStats,
+) -> Result<(), String> {
+    if dst.is_file() {
+        retry_io(&mut stats.retries, || fs::remove_file(dst))
+            .map_err(|e| format!("Failed to replace {:?}: {}", dst, e))?;
+    }
+    copy_file(src, dst, options, stats)
+}
+
+/// `fs::copy` through a user-sized buffer; keeps the permissions like `fs::copy` does
+fn buffered_copy(src: &Path, dst: &Path, buffer_size: usize) -> io::Result<u64> {
+    let reader = fs::File::open(src)?;
+    let permissions = reader.metadata()?.permissions();
+    let mut reader = io::BufReader::with_capacity(buffer_size, reader);
+    let mut writer = io::BufWriter::with_capacity(buffer_size, fs::File::create(dst)?);
+    let bytes = io::copy(&mut reader, &mut writer)?;
+    io::Write::flush(&mut writer)?;
+    fs::set_permissions(dst, permissions)?;
+    Ok(bytes)
+}
+
+/// Sleeps until the bytes written so far fit the configured rate. Works per file, so a single
+/// large file still goes at full speed and the pause comes after it.
+fn throttle_copy(options: &CopyOptions, stats: &mut CopyStats, bytes: u64) {
+    let Some(limit) = options.max_bytes_per_sec else {
+        return;
+    };
+
+    let (started, written) = stats.throttle.get_or_insert_with(|| (Instant::now(), 0));
+    *written += bytes;
+    let expected = Duration::from_secs_f64(*written as f64 / limit as f64);
+    if let Some(ahead) = expected.checked_sub(started.elapsed()) {
+        std::thread::sleep(ahead);
+    }
+}
+
+fn copy_dir_recursive(
+    src: &Path,
+    dst: &Path,
+    options: &CopyOptions,
+    stats: &mut CopyStats,
+) -> Result<(), String> {
+    fs::create_dir_all(dst).map_err(|e| format!("Failed to create dir {:?}: {}", dst, e))?;
+    copy_dir_contents(src, dst, options, stats)?;
+    match dst.parent() {
+        Some(parent) if options.durable => sync_dir(parent),
+        _ => Ok(()),
+    }
+}
+
+/// Tests make copying a file with this name panic, to check what an unwind leaves behind
+#[cfg(test)]
+const PANIC_ON_COPY: &str = "panic-on-copy.sav";
+
+/// Copies the entries of `src` into the existing folder `dst`. Nested folders are created
+/// with a single `create_dir` each since their parent is known to exist, and entry types
+/// come from the directory listing, so no extra stat calls are made per file.
+fn copy_dir_contents(
+    src: &Path,
+    dst: &Path,
+    options: &CopyOptions,
+    stats: &mut CopyStats,
+) -> Result<(), String> {
+    let entries = fs::read_dir(src).map_err(|e| format!("Failed to read dir {:?}: {}", src, e))?;
+
+    for entry in entries {
+        let entry = entry.map_err(|e| format!("Failed to read entry: {}", e))?;
+        let src_path = entry.path();
+        let dst_path = dst.join(entry.file_name());
+
+        // DirEntry::file_type doesn't follow links; on Windows junctions report as symlinks too
+        let file_type = entry.file_type().ok();
+        let is_link = file_type.is_some_and(|t| t.is_symlink());
+        if is_link && options.skip_reparse_points {
+            stats.skipped_links.push(src_path);
+            continue;
+        }
+
+        if options.skip_remotecache && is_remotecache(&src_path) {
+            stats.skipped_remotecaches += 1;
+            continue;
+        }
+
+        // Links are followed, so only they need a stat to tell folders from files
+        let is_dir = match file_type {
+            Some(t) if !t.is_symlink() => t.is_dir(),
+            _ => src_path.is_dir(),
+        };
+        let copied = if is_dir {
+            match fs::create_dir(&dst_path) {
+                Err(e) if e.kind() != io::ErrorKind::AlreadyExists => {
+                    Err(format!("Failed to create dir {:?}: {}", dst_path, e))
+                }
+                _ => copy_dir_contents(&src_path, &dst_path, options, stats),
+            }
+        } else {
+            #[cfg(test)]
+            if entry.file_name() == PANIC_ON_COPY {
+                panic!("Copy of {:?} panicked", src_path);
+            }
+            copy_file(&src_path, &dst_path, options, stats).inspect_err(|e| log::warn!("{}", e))
+        };
+        copied?;
+    }
+
+    if options.durable {
+        sync_dir(dst)?;
+    }
+    Ok(())
+}
+
+/// Emits a `backup-scanned` event per backup as it is discovered, returning the total size
+#[tauri::command]
+async fn scan_backups(app: AppHandle, userdata_path: String, steam_path: String) -> u64 {
+    let ud = PathBuf::from(&userdata_path);
+    let steam = Path::new(&steam_path);
+    let steamapps_dirs = find_all_steamapps_dirs(steam);
+    let appinfo_games = get_appinfo_games(steam);
+    let drives = Drives::list();
+
+    let mut running_total_bytes: u64 = 0;
+    for snapshot in list_backup_snapshots(&ud) {
+        let profile = match discover_backup_profile(
+            &snapshot,
+            &ud,
+            steam,
+            &appinfo_games,
+            &steamapps_dirs,
+            &drives,
+        ) {
+            Some(p) => p,
+            None => continue,
+        };
+
+        let size_bytes = dir_size_only(&snapshot.path);
+        running_total_bytes += size_bytes;
+
+        let _ = app.emit(
+            "backup-scanned",
+            BackupScanEvent {
+                profile,
+                size_bytes,
+                running_total_bytes,
+            },
+        );
+    }
+
+    running_total_bytes
+}
+
+/// Renames the snapshot to carry the new label (an empty one removes it), returning the
+/// backup's new id
+#[tauri::command]
+fn set_backup_label(
+    userdata_path: String,
+    backup_id: String,
+    label: String,
+) -> Result<String, String> {
+    sanitize_backup_id(&backup_id)?;
+    let _guard = SwapGuard::acquire().ok_or(SWAP_BUSY_MESSAGE)?;
+    let backup_path = profile_base_path(Path::new(&userdata_path), &backup_id, true);
+    let snapshot = BackupSnapshot::from_path(&backup_path)
+        .filter(|_| backup_path.is_dir())
+        .ok_or("Backup not found")?;
+
+    let name = snapshot_name(snapshot.created, sanitize_backup_label(&label).as_deref());
+    if name != snapshot.name {
+        fs::rename(&backup_path, backup_path.with_file_name(&name))
+            .map_err(|e| format!("Failed to rename backup: {}", e))?;
+    }
+    Ok(format!("{}/{}", snapshot.account_id, name))
+}
+
+/// Where `check_games_running` looks for the game's executables, to verify detection for a game
+#[tauri::command]
+fn get_game_install_dir(steam_path: String, game_id: String) -> Option<String> {
+    sanitize_id(&game_id).ok()?;
+    game_install_dir(Path::new(&steam_path), &game_id).map(|dir| normalize_path(&dir))
+}
+
+#[tauri::command]
+fn check_games_running(steam_path: String, game_ids: Vec<String>) -> bool {
+    if game_ids.is_empty() {
+        return false;
+    }
+
+    let steam = Path::new(&steam_path);
+    let appinfo_games = get_appinfo_games(steam);
+
+    // (lowercased executable names, install dir) per game
+    let games: Vec<(Vec<String>, Option<PathBuf>)> = game_ids
+        .iter()
+        .filter(|game_id| sanitize_id(game_id).is_ok())
+        .map(|game_id| {
+            let exe_names: Vec<String> = appinfo_games
+                .get(game_id)
+                .map(|info| info.executables.iter().map(|e| e.to_lowercase()).collect())
+                .unwrap_or_default();
+            (exe_names, game_install_dir(steam, game_id))
+        })
+        .filter(|(exe_names, install_dir)| !exe_names.is_empty() || install_dir.is_some())
+        .collect();
+    if games.is_empty() {
+        return false;
+    }
+
+    let mut sys = System::new();
+    sys.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
+    sys.processes().values().any(|p| {
+        games
+            .iter()
+            .any(|(exe_names, install_dir)| is_game_process(p, exe_names, install_dir.as_deref()))
+    })
+}
+
+/// A process whose binary lives in the game's install dir always counts. A matching name only
+/// counts when it can't be ruled out as an unrelated program of the same name: its binary is
+/// unknown, the install dir is, or the binary has another name, as when Proton's wine loader
+/// runs a Windows game.
+fn is_game_process(p: &sysinfo::Process, exe_names: &[String], install_dir: Option<&Path>) -> bool {
+    let exe = p.exe().filter(|exe| !exe.as_os_str().is_empty());
+    if let (Some(dir), Some(exe)) = (install_dir, exe) {
+        if exe.starts_with(dir) {
+            return true;
+        }
+    }
+
+    let name = p.name().to_string_lossy().to_lowercase();
+    if !exe_names.contains(&name) {
+        return false;
+    }
+    match (install_dir, exe) {
+        (Some(_), Some(exe)) => exe
+            .file_name()
+            .is_none_or(|file| file.to_string_lossy().to_lowercase() != name),
+        _ => true,
+    }
+}
+
+/// Lowercased names of all running processes
+fn running_process_names() -> std::collections::HashSet<String> {
+    let mut sys = System::new();
+    sys.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
+    sys.processes()
+        .values()
+        .map(|p| p.name().to_string_lossy().to_lowercase())
+        .collect()
+}
+
+/// Process names of the Steam client on each platform
+const STEAM_PROCESS_NAMES: [&str; 3] = ["steam.exe", "steam", "steam_osx"];
+
+fn is_steam_process(p: &sysinfo::Process) -> bool {
+    let pname = p.name().to_string_lossy();
+    STEAM_PROCESS_NAMES
+        .iter()
+        .any(|name| pname.eq_ignore_ascii_case(name))
+}
+
+#[tauri::command]
+fn is_steam_running() -> bool {
+    let mut sys = System::new();
+    sys.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
+    sys.processes().values().any(is_steam_process)
+}
 
-            // Step 2: Delete target game folder
-            if target_game.exists() {
-                if let Err(e) = fs::remove_dir_all(&target_game) {
-                    details.push(format!(
-                        "Error: Failed to clear target {}/{}: {}",
-                        target_id, game_id, e
-                    ));
-                    continue;
-                }
-            }
+/// How long `close_steam` waits for Steam to exit once asked to
+const STEAM_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(30);
 
-            // Step 3: Copy source game folder to target
-            if let Err(e) = fs::create_dir_all(&target_game) {
-                details.push(format!(
-                    "Error: Failed to create target dir for {}/{}: {}",
-                    target_id, game_id, e
-                ));
-                continue;
-            }
+/// Asks Steam to exit the way its own Exit menu does, so it can't lock or revert userdata edits,
+/// and waits for it to finish. Nothing is killed: games started from Steam keep running, since
+/// killing them could corrupt the saves about to be swapped, and are returned by name so the
+/// user can close them.
+#[tauri::command]
+fn close_steam() -> Result<CloseSteamResult, String> {
+    let mut sys = System::new();
+    sys.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
 
-            match copy_dir_recursive(&source_game, &target_game) {
-                Ok(_) => details.push(format!(
-                    "Successfully swapped game {} for profile {}",
-                    game_id, target_id
-                )),
-                Err(e) => {
-                    details.push(format!(
-                        "Error: Failed to copy game {} to {}: {}",
-                        game_id, target_id, e
-                    ));
-                    continue;
-                }
-            }
+    let steam_pids: Vec<sysinfo::Pid> = sys
+        .processes()
+        .iter()
+        .filter(|(_, p)| is_steam_process(p))
+        .map(|(pid, _)| *pid)
+        .collect();
+    if steam_pids.is_empty() {
+        return Ok(CloseSteamResult::default());
+    }
+    // Steam's own helpers (steamwebhelper etc.) exit with it, anything else it started is a game
+    let launched: Vec<sysinfo::Pid> = sys
+        .processes()
+        .iter()
+        .filter(|(_, p)| {
+            p.parent()
+                .is_some_and(|parent| steam_pids.contains(&parent))
+        })
+        .filter(|(_, p)| {
+            !p.name()
+                .to_string_lossy()
+                .to_lowercase()
+                .starts_with("steam")
+        })
+        .map(|(pid, _)| *pid)
+        .collect();
+
+    let asked = steam_pids
+        .iter()
+        .filter_map(|pid| sys.process(*pid))
+        .filter(|process| request_steam_shutdown(process))
+        .count();
+    if asked == 0 {
+        return Err("Could not ask Steam to exit, try closing Steam manually".to_string());
+    }
+
+    let deadline = Instant::now() + STEAM_SHUTDOWN_TIMEOUT;
+    let steam_running = |sys: &System| {
+        steam_pids
+            .iter()
+            .any(|pid| sys.process(*pid).is_some_and(is_steam_process))
+    };
+    while steam_running(&sys) {
+        if Instant::now() >= deadline {
+            return Err(format!(
+                "Steam didn't exit within {} seconds, try closing Steam manually",
+                STEAM_SHUTDOWN_TIMEOUT.as_secs()
+            ));
         }
+        std::thread::sleep(Duration::from_millis(250));
+        sys.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
     }
 
-    let all_success = !details.iter().any(|d| d.starts_with("Error:"));
+    let mut games_running: Vec<String> = launched
+        .iter()
+        .filter_map(|pid| sys.process(*pid))
+        .map(|p| p.name().to_string_lossy().to_string())
+        .collect();
+    games_running.sort();
+    games_running.dedup();
+    Ok(CloseSteamResult { games_running })
+}
 
-    SwapResult {
-        success: all_success,
-        message: if all_success {
-            "All games swapped successfully!".to_string()
-        } else {
-            "Some operations failed. Check details.".to_string()
-        },
-        details,
+/// SIGTERM, which Steam handles like its Exit menu item
+#[cfg(unix)]
+fn request_steam_shutdown(process: &sysinfo::Process) -> bool {
+    process.kill_with(sysinfo::Signal::Term).unwrap_or(false)
+}
+
+/// Windows has no SIGTERM; Steam's `-shutdown` switch tells the running client to exit
+#[cfg(not(unix))]
+fn request_steam_shutdown(process: &sysinfo::Process) -> bool {
+    process.exe().is_some_and(|exe| {
+        std::process::Command::new(exe)
+            .arg("-shutdown")
+            .spawn()
+            .is_ok()
+    })
+}
+
+const LOG_FILE_NAME: &str = "nether-swap";
+
+#[tauri::command]
+fn open_log_file(app: AppHandle) -> Result<(), String> {
+    let log_path = app
+        .path()
+        .app_log_dir()
+        .map_err(|e| e.to_string())?
+        .join(format!("{}.log", LOG_FILE_NAME));
+    if !log_path.exists() {
+        return Err("No log file has been written yet".to_string());
     }
+    app.opener()
+        .open_path(normalize_path(&log_path), None::<&str>)
+        .map_err(|e| e.to_string())
 }
 
-fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<(), String> {
-    if !dst.exists() {
-        fs::create_dir_all(dst).map_err(|e| format!("Failed to create dir {:?}: {}", dst, e))?;
+#[tauri::command]
+fn diagnostics() -> DiagnosticsReport {
+    let mut report = DiagnosticsReport {
+        os: std::env::consts::OS.to_string(),
+        steam_path: None,
+        steamapps_dirs: vec![],
+        appinfo_exists: false,
+        appinfo_parsed: false,
+        appinfo_entry_count: 0,
+        appinfo_warning: None,
+        appinfo_version: None,
+        userdata_exists: false,
+        profile_count: 0,
+    };
+
+    let steam_path = match detect_steam_path() {
+        Some(p) => p,
+        None => return report,
+    };
+    report.steam_path = Some(normalize_path(&steam_path));
+
+    let steamapps_dirs = find_all_steamapps_dirs(&steam_path);
+    report.steamapps_dirs = steamapps_dirs.iter().map(|d| normalize_path(d)).collect();
+
+    report.appinfo_exists = steam_path.join("appcache").join("appinfo.vdf").exists();
+    if report.appinfo_exists {
+        let appinfo_games = get_appinfo_games(&steam_path);
+        report.appinfo_parsed = !appinfo_games.is_empty();
+        report.appinfo_entry_count = appinfo_games.len();
+        report.appinfo_warning = APP_INFO_WARNING.lock().unwrap().clone();
+        report.appinfo_version =
+            appinfo_version(&steam_path.join("appcache").join("appinfo.vdf")).ok();
     }
 
-    let entries = fs::read_dir(src).map_err(|e| format!("Failed to read dir {:?}: {}", src, e))?;
+    if let Some(userdata_path) = find_userdata_path(&steam_path) {
+        report.userdata_exists = true;
+        report.profile_count = discover_profiles(&userdata_path, &steam_path, &steamapps_dirs)
+            .iter()
+            .filter(|p| !p.is_backup)
+            .count();
+    }
 
-    for entry in entries {
-        let entry = entry.map_err(|e| format!("Failed to read entry: {}", e))?;
-        let src_path = entry.path();
-        let dst_path = dst.join(entry.file_name());
+    report
+}
 
-        if src_path.is_dir() {
-            copy_dir_recursive(&src_path, &dst_path)?;
-        } else {
-            fs::copy(&src_path, &dst_path)
-                .map_err(|e| format!("Failed to copy {:?} -> {:?}: {}", src_path, dst_path, e))?;
+#[tauri::command]
+fn get_time_preference() -> TimePreference {
+    *TIME_PREFERENCE.lock().unwrap()
+}
+
+/// Changes how timestamps are formatted in everything returned from now on
+#[tauri::command]
+fn set_time_preference(app: AppHandle, preference: TimePreference) -> Result<(), String> {
+    let store = app.store(SETTINGS_STORE).map_err(|e| e.to_string())?;
+    let value = serde_json::to_value(preference).map_err(|e| e.to_string())?;
+    store.set(TIME_PREFERENCE_KEY, value);
+    store.save().map_err(|e| e.to_string())?;
+
+    *TIME_PREFERENCE.lock().unwrap() = preference;
+    Ok(())
+}
+
+/// The whole settings store as a JSON object, for carrying the app's configuration to another
+/// machine with `import_config`
+#[tauri::command]
+fn export_config(app: AppHandle) -> String {
+    let entries: serde_json::Map<String, serde_json::Value> = app
+        .store(SETTINGS_STORE)
+        .map(|store| store.entries().into_iter().collect())
+        .unwrap_or_default();
+    serde_json::to_string_pretty(&entries).unwrap_or_default()
+}
+
+/// Replaces the settings store with an `export_config` dump. Every known key is validated and
+/// every `...Path` value must exist on this machine before anything is written.
+#[tauri::command]
+fn import_config(app: AppHandle, json: String) -> Result<(), String> {
+    let entries: serde_json::Map<String, serde_json::Value> =
+        serde_json::from_str(&json).map_err(|e| format!("Invalid configuration: {}", e))?;
+
+    let mut backups_dir = None;
+    let mut time_preference = TimePreference::default();
+    for (key, value) in &entries {
+        let invalid = |e: String| format!("Invalid {} in configuration: {}", key, e);
+        match key.as_str() {
+            BACKUPS_DIR_KEY => {
+                let name = value
+                    .as_str()
+                    .ok_or_else(|| invalid("not a string".into()))?;
+                validate_backups_dir_name(name).map_err(invalid)?;
+                backups_dir = Some(name.to_string());
+            }
+            TIME_PREFERENCE_KEY => {
+                time_preference =
+                    serde_json::from_value(value.clone()).map_err(|e| invalid(e.to_string()))?;
+            }
+            SELECTION_KEY => {
+                serde_json::from_value::<SavedSelection>(value.clone())
+                    .map_err(|e| invalid(e.to_string()))?;
+            }
+            SWAP_STATS_KEY => {
+                serde_json::from_value::<Vec<SwapStats>>(value.clone())
+                    .map_err(|e| invalid(e.to_string()))?;
+            }
+            _ if key.to_lowercase().ends_with("path") => {
+                let path = value
+                    .as_str()
+                    .ok_or_else(|| invalid("not a string".into()))?;
+                if !Path::new(path).exists() {
+                    return Err(invalid(format!("{} does not exist on this machine", path)));
+                }
+            }
+            _ => {}
         }
     }
 
+    let store = app.store(SETTINGS_STORE).map_err(|e| e.to_string())?;
+    store.clear();
+    for (key, value) in entries {
+        store.set(key, value);
+    }
+    store.save().map_err(|e| e.to_string())?;
+
+    *BACKUPS_DIR_NAME.lock().unwrap() = backups_dir;
+    *TIME_PREFERENCE.lock().unwrap() = time_preference;
     Ok(())
 }
 
+/// Renames the backups folder used from now on; `None` restores the default. Existing backups
+/// under the old name are not moved.
 #[tauri::command]
-fn check_games_running(steam_path: String, game_ids: Vec<String>) -> bool {
-    if game_ids.is_empty() {
-        return false;
+fn set_backups_dir_name(app: AppHandle, name: Option<String>) -> Result<String, String> {
+    let name = name.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+    if let Some(ref n) = name {
+        validate_backups_dir_name(n)?;
     }
 
-    let appinfo_games = get_appinfo_games(Path::new(&steam_path));
-
-    let mut exe_names: Vec<String> = Vec::new();
-    for game_id in &game_ids {
-        if let Some(info) = appinfo_games.get(game_id) {
-            exe_names.extend(info.executables.iter().cloned());
+    let store = app.store(SETTINGS_STORE).map_err(|e| e.to_string())?;
+    match name {
+        Some(ref n) => store.set(BACKUPS_DIR_KEY, n.clone()),
+        None => {
+            store.delete(BACKUPS_DIR_KEY);
         }
     }
+    store.save().map_err(|e| e.to_string())?;
 
-    if exe_names.is_empty() {
-        return false;
+    *BACKUPS_DIR_NAME.lock().unwrap() = name;
+    Ok(backups_dir_name())
+}
+
+/// Emits `profiles-changed` whenever something under userdata changes. Calling it again
+/// replaces the previous watcher.
+#[tauri::command]
+fn start_watching(app: AppHandle, userdata_path: String) -> Result<(), String> {
+    let ud = PathBuf::from(&userdata_path);
+    if !ud.is_dir() {
+        return Err("Userdata path does not exist".to_string());
     }
 
-    let mut sys = System::new();
-    sys.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
+    let mut debouncer = new_debouncer(WATCH_DEBOUNCE, move |res: DebounceEventResult| {
+        let Ok(events) = res else {
+            return;
+        };
+        // The throughput benchmark's scratch files come and go without changing any profile
+        let relevant = events.iter().any(|e| {
+            !e.path
+                .file_name()
+                .map(|n| n.to_string_lossy().starts_with(BENCHMARK_FILE_PREFIX))
+                .unwrap_or(false)
+        });
+        if relevant {
+            let _ = app.emit("profiles-changed", ());
+        }
+    })
+    .map_err(|e| format!("Failed to start watcher: {}", e))?;
 
-    sys.processes()
-        .values()
-        .any(|p| {
-            let pname = p.name().to_string_lossy();
-            exe_names.iter().any(|exe| pname.eq_ignore_ascii_case(exe))
-        })
+    debouncer
+        .watcher()
+        .watch(&ud, RecursiveMode::Recursive)
+        .map_err(|e| format!("Failed to watch {}: {}", normalize_path(&ud), e))?;
+
+    *USERDATA_WATCHER.lock().unwrap() = Some(debouncer);
+    Ok(())
+}
+
+#[tauri::command]
+fn stop_watching() {
+    USERDATA_WATCHER.lock().unwrap().take();
+}
+
+#[tauri::command]
+fn last_swap_stats(app: AppHandle) -> Option<SwapStats> {
+    read_swap_stats(&app).pop()
+}
+
+/// The last `MAX_SWAP_STATS` swaps, newest first
+#[tauri::command]
+fn swap_stats_history(app: AppHandle) -> Vec<SwapStats> {
+    let mut history = read_swap_stats(&app);
+    history.reverse();
+    history
+}
+
+#[tauri::command]
+fn save_selection(app: AppHandle, state: SavedSelection) -> Result<(), String> {
+    let store = app.store(SETTINGS_STORE).map_err(|e| e.to_string())?;
+    let value = serde_json::to_value(&state).map_err(|e| e.to_string())?;
+    store.set(SELECTION_KEY, value);
+    store.save().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn load_selection(
+    app: AppHandle,
+    userdata_path: String,
+    steam_path: String,
+) -> Option<SavedSelection> {
+    let store = app.store(SETTINGS_STORE).ok()?;
+    let mut selection: SavedSelection = serde_json::from_value(store.get(SELECTION_KEY)?).ok()?;
+
+    let ud = PathBuf::from(&userdata_path);
+    let steam = Path::new(&steam_path);
+    let steamapps_dirs = find_all_steamapps_dirs(steam);
+    let profiles = discover_profiles(&ud, steam, &steamapps_dirs);
+
+    // Without its source the rest of the selection is meaningless
+    let source_exists = profiles
+        .iter()
+        .any(|p| p.id == selection.source.id && p.is_backup == selection.source.is_backup);
+    if !source_exists {
+        return None;
+    }
+
+    selection
+        .targets
+        .retain(|tid| profiles.iter().any(|p| &p.id == tid && !p.is_backup));
+
+    let appinfo_games = get_appinfo_games(steam);
+    let source_games = list_profile_games(
+        &profile_base_path(&ud, &selection.source.id, selection.source.is_backup),
+        &appinfo_games,
+        &steamapps_dirs,
+    );
+    selection
+        .games
+        .retain(|gid| source_games.iter().any(|g| &g.id == gid));
+
+    Some(selection)
+}
+
+#[tauri::command]
+fn app_version() -> String {
+    env!("CARGO_PKG_VERSION").to_string()
+}
+
+#[tauri::command]
+async fn check_for_update(app: AppHandle) -> Result<UpdateStatus, String> {
+    let update = app
+        .updater()
+        .map_err(|e| e.to_string())?
+        .check()
+        .await
+        .map_err(|e| format!("Update check failed: {}", e))?;
+
+    Ok(UpdateStatus {
+        current_version: app_version(),
+        update_available: update.is_some(),
+        version: update.as_ref().map(|u| u.version.clone()),
+        notes: update.and_then(|u| u.body),
+    })
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -936,15 +6884,773 @@ pub fn run() {
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_store::Builder::new().build())
+        .plugin(
+            tauri_plugin_log::Builder::new()
+                .level(log::LevelFilter::Info)
+                .targets([
+                    tauri_plugin_log::Target::new(tauri_plugin_log::TargetKind::Stdout),
+                    tauri_plugin_log::Target::new(tauri_plugin_log::TargetKind::LogDir {
+                        file_name: Some(LOG_FILE_NAME.to_string()),
+                    }),
+                ])
+                .max_file_size(5 * 1024 * 1024)
+                .rotation_strategy(tauri_plugin_log::RotationStrategy::KeepOne)
+                .build(),
+        )
+        .setup(|app| {
+            if let Ok(cache_dir) = app.path().app_cache_dir() {
+                let _ = APP_INFO_DISK_CACHE.set(cache_dir.join(APP_INFO_CACHE_FILE));
+            }
+            if let Ok(store) = app.store(SETTINGS_STORE) {
+                let saved = store
+                    .get(BACKUPS_DIR_KEY)
+                    .and_then(|v| v.as_str().map(str::to_string))
+                    .filter(|n| validate_backups_dir_name(n).is_ok());
+                *BACKUPS_DIR_NAME.lock().unwrap() = saved;
+
+                let time_preference = store
+                    .get(TIME_PREFERENCE_KEY)
+                    .and_then(|v| serde_json::from_value(v).ok());
+                if let Some(preference) = time_preference {
+                    *TIME_PREFERENCE.lock().unwrap() = preference;
+                }
+            }
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             detect_steam,   
             validate_steam_path,
+            set_userdata_path,
             get_profiles,
+            get_profile,
+            current_user,
+            all_known_users,
+            profile_details,
+            profiles_with_game,
+            overview,
             get_games_for_profile,
+            get_games_for_backup,
+            uninstalled_with_saves,
+            resolve_games_by_pattern,
+            compare_profiles,
+            diff_game,
+            backup_drift,
+            largest_files,
+            game_fingerprint,
+            confirm_swap,
             get_swap_summary,
+            estimate_swap,
+            swap_file_list,
             execute_swap,
+            execute_swap_batch,
+            import_as_source,
+            clone_profile,
+            merge_profiles,
+            relocate_game_save,
+            export_swap_report,
+            backup_profile,
+            delete_backup,
+            verify_backup,
+            archive_backup,
+            dedupe_backups,
+            backup_age_histogram,
+            restore_from_archive,
+            cloud_risk_report,
+            target_safety,
+            profile_cloud_summary,
+            get_swap_journal,
+            is_swap_in_progress,
+            last_swap_stats,
+            game_executables,
+            resolve_game_name,
+            get_game_install_dir,
+            get_proton_saves,
+            appinfo_warning,
+            refresh_appinfo_cache,
+            swap_stats_history,
+            undo_swap,
+            orphaned_games,
+            delete_orphaned_games,
             check_games_running,
+            is_steam_running,
+            close_steam,
+            set_backup_label,
+            scan_backups,
+            set_backups_dir_name,
+            get_time_preference,
+            set_time_preference,
+            export_config,
+            import_config,
+            start_watching,
+            stop_watching,
+            save_selection,
+            load_selection,
+            diagnostics,
+            open_log_file,
+            app_version,
+            check_for_update,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Held by tests calling commands that take the swap guard, which would otherwise fail
+    /// when they run at the same time
+    static SWAP_GUARD_TESTS: Mutex<()> = Mutex::new(());
+
+    /// A fresh, empty folder under the system temp dir, unique per test
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("nether-swap-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn appinfo_header_versions() {
+        let dir = scratch_dir("appinfo-header");
+        let write_header = |name: &str, magic: u32| {
+            let path = dir.join(name);
+            fs::write(&path, magic.to_le_bytes()).unwrap();
+            path
+        };
+
+        for (magic, version) in [
+            (0x0756_4427, 0x27),
+            (0x0756_4428, 0x28),
+            (0x0756_4429, 0x29),
+        ] {
+            let path = write_header("supported.vdf", magic);
+            assert_eq!(appinfo_version(&path), Ok(version));
+            assert!(SUPPORTED_APPINFO_VERSIONS.contains(&version));
+        }
+
+        let old = write_header("old.vdf", 0x0756_4426);
+        assert_eq!(appinfo_version(&old), Ok(0x26));
+        let err = read_appinfo_vdf(&old).unwrap_err();
+        assert!(err.contains("unsupported format version 26"), "{}", err);
+
+        let garbage = write_header("garbage.vdf", 0x1234_5678);
+        assert!(appinfo_version(&garbage)
+            .unwrap_err()
+            .contains("unrecognized header"));
+        assert!(appinfo_version(&dir.join("missing.vdf")).is_err());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn copy_that_panics_partway_restores_the_targets() {
+        let dir = scratch_dir("copy-panic");
+        let ud = dir.join("userdata");
+        let source_game = ud.join("111").join("440");
+        let restored_game = ud.join("222").join("440");
+        let new_game = ud.join("333").join("440");
+        fs::create_dir_all(&source_game).unwrap();
+        fs::create_dir_all(&restored_game).unwrap();
+        fs::create_dir_all(ud.join("333")).unwrap();
+        fs::write(source_game.join("a.sav"), b"source").unwrap();
+        fs::write(source_game.join(PANIC_ON_COPY), b"source").unwrap();
+        fs::write(restored_game.join("a.sav"), b"target").unwrap();
+
+        let panicked = std::panic::catch_unwind(|| {
+            swap_into_targets(
+                &ud,
+                &ud.join("111"),
+                &["222".to_string(), "333".to_string()],
+                &["440".to_string()],
+                &SwapOptions::default(),
+                &mut Vec::new(),
+            )
+        });
+        assert!(panicked.is_err());
+        // The target with data gets it back from its backup, the one without has no folder
+        assert_eq!(fs::read(restored_game.join("a.sav")).unwrap(), b"target");
+        assert!(!restored_game.join(PANIC_ON_COPY).exists());
+        assert!(!new_game.exists());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn failed_copy_rolls_back_target_game() {
+        let dir = scratch_dir("copy-rollback");
+        let ud = dir.join("userdata");
+        let source_game = ud.join("111").join("440");
+        let target_game = ud.join("222").join("440");
+        fs::create_dir_all(&source_game).unwrap();
+        fs::create_dir_all(&target_game).unwrap();
+        fs::write(source_game.join("a.sav"), b"source").unwrap();
+        // A dangling link can't be copied, so the copy fails after a.sav
+        std::os::unix::fs::symlink(dir.join("missing"), source_game.join("z.sav")).unwrap();
+        fs::write(target_game.join("a.sav"), b"target").unwrap();
+
+        let options = SwapOptions {
+            stop_on_error: true,
+            ..SwapOptions::default()
+        };
+        let mut journal = Vec::new();
+        let result = swap_into_targets(
+            &ud,
+            &ud.join("111"),
+            &["222".to_string()],
+            &["440".to_string()],
+            &options,
+            &mut journal,
+        );
+        assert!(!result.success);
+        assert_eq!(fs::read(target_game.join("a.sav")).unwrap(), b"target");
+        assert!(!target_game.join("z.sav").exists());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn partly_cleared_target_stops_sharing_files_with_its_backup() {
+        let dir = scratch_dir("unlink-backup");
+        let snapshot = dir.join("snapshot");
+        let target_game = dir.join("target").join("440");
+        fs::create_dir_all(target_game.join("cfg")).unwrap();
+        fs::create_dir_all(&snapshot).unwrap();
+        fs::write(target_game.join("a.sav"), b"old").unwrap();
+        fs::write(target_game.join("cfg").join("b.cfg"), b"old").unwrap();
+
+        let mut stats = CopyStats::default();
+        let mut details = Vec::new();
+        assert!(link_game_to_backup(
+            &snapshot,
+            &target_game,
+            "222",
+            "440",
+            &mut stats,
+            &mut details
+        ));
+        // As if clearing the target stopped after removing a.sav
+        fs::remove_file(target_game.join("a.sav")).unwrap();
+        let backup_game = snapshot.join("440");
+        assert_eq!(
+            unlink_backup_from_target(&backup_game, &target_game).unwrap(),
+            1
+        );
+
+        fs::write(target_game.join("cfg").join("b.cfg"), b"new").unwrap();
+        assert_eq!(
+            fs::read(backup_game.join("cfg").join("b.cfg")).unwrap(),
+            b"old"
+        );
+        assert_eq!(fs::read(backup_game.join("a.sav")).unwrap(), b"old");
+        assert_eq!(
+            unlink_backup_from_target(&backup_game, &target_game).unwrap(),
+            0
+        );
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn undo_restores_targets_after_a_move_strategy_swap() {
+        let dir = scratch_dir("undo-move");
+        let ud = dir.join("userdata");
+        let target_game = ud.join("222").join("440");
+        let new_game = ud.join("333").join("440");
+        fs::create_dir_all(ud.join("111").join("440")).unwrap();
+        fs::create_dir_all(&target_game).unwrap();
+        fs::create_dir_all(ud.join("333")).unwrap();
+        fs::write(ud.join("111").join("440").join("a.sav"), b"source").unwrap();
+        fs::write(target_game.join("a.sav"), b"target").unwrap();
+
+        // The scratch folder is on one volume, so the target's folder is moved into its backup
+        let options = SwapOptions {
+            backup_strategy: BackupStrategy::Auto,
+            ..SwapOptions::default()
+        };
+        let mut journal = Vec::new();
+        let result = swap_into_targets(
+            &ud,
+            &ud.join("111"),
+            &["222".to_string(), "333".to_string()],
+            &["440".to_string()],
+            &options,
+            &mut journal,
+        );
+        assert!(result.success, "{:?}", result.details);
+        assert!(result.details.iter().any(|d| d.contains("222: move")));
+        assert!(journal[0].games[0].had_data);
+        assert!(!journal[1].games[0].had_data);
+        assert_eq!(fs::read(new_game.join("a.sav")).unwrap(), b"source");
+
+        let undone = undo_journal_entry(&ud, &new_journal_entry("111".to_string(), journal));
+        assert!(undone.success, "{:?}", undone.details);
+        assert_eq!(fs::read(target_game.join("a.sav")).unwrap(), b"target");
+        assert!(!new_game.exists());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn library_folders_both_formats() {
+        let nested = r#"
+"libraryfolders"
+{
+	"0"
+	{
+		"path"		"C:\\Program Files (x86)\\Steam"
+		"label"		""
+		"apps"
+		{
+			"228980"		"389437756"
+			"440"		"27498172342"
+		}
+	}
+	"1"
+	{
+		"path"		"/mnt/games/SteamLibrary"
+		"apps"
+		{
+		}
+	}
+}
+"#;
+        assert_eq!(
+            parse_library_folder_paths(nested),
+            vec![
+                PathBuf::from(r"C:\Program Files (x86)\Steam"),
+                PathBuf::from("/mnt/games/SteamLibrary"),
+            ]
+        );
+
+        let old = r#"
+"LibraryFolders"
+{
+	"TimeNextStatsReport"		"1561832478"
+	"ContentStatsID"		"-158337411"
+	"1"		"D:\\Lib"
+	"2"		"/media/games/Steam"
+}
+"#;
+        assert_eq!(
+            parse_library_folder_paths(old),
+            vec![
+                PathBuf::from(r"D:\Lib"),
+                PathBuf::from("/media/games/Steam")
+            ]
+        );
+    }
+
+    #[test]
+    fn library_folders_skip_missing_and_duplicate_libraries() {
+        let dir = scratch_dir("library-folders");
+        let steam = dir.join("Steam");
+        let library = dir.join("Library");
+        fs::create_dir_all(steam.join("steamapps")).unwrap();
+        fs::create_dir_all(library.join("steamapps")).unwrap();
+        let vdf = format!(
+            "\"libraryfolders\"\n{{\n\t\"0\"\n\t{{\n\t\t\"path\"\t\t\"{}\"\n\t}}\n\t\"1\"\n\t{{\n\t\t\"path\"\t\t\"{}\"\n\t}}\n\t\"2\"\n\t{{\n\t\t\"path\"\t\t\"{}\"\n\t}}\n\t\"3\"\t\t\"{}\"\n}}\n",
+            steam.display(),
+            library.display(),
+            dir.join("Unplugged").display(),
+            library.display(),
+        );
+        fs::write(steam.join("steamapps").join("libraryfolders.vdf"), vdf).unwrap();
+
+        let dirs = find_all_steamapps_dirs(&steam);
+        assert_eq!(
+            dirs[..2],
+            [steam.join("steamapps"), library.join("steamapps")]
+        );
+        assert!(!dirs.iter().any(|d| d.starts_with(dir.join("Unplugged"))));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn traversal_ids_and_paths_are_rejected() {
+        for id in [
+            "../../etc",
+            "..",
+            ".",
+            "",
+            "12/../34",
+            r"12\34",
+            "C:12",
+            "12\0",
+        ] {
+            assert!(sanitize_id(id).is_err(), "{:?}", id);
+        }
+        assert!(sanitize_id("abc").is_err());
+        assert_eq!(sanitize_id("12345678"), Ok("12345678"));
+        assert!(sanitize_game_ids(&[ALL_GAMES.to_string(), "440".to_string()]).is_ok());
+        assert!(sanitize_ids(&[ALL_GAMES.to_string()]).is_err());
+        let options = SwapOptions {
+            allow_new_targets: true,
+            ..Default::default()
+        };
+        let all = [ALL_GAMES.to_string()];
+        assert!(validate_swap_selection(&all, &["440".to_string()], &options).is_err());
+        assert!(validate_swap_selection(&["123".to_string()], &all, &options).is_ok());
+        assert!(sanitize_ids(&["440".to_string(), "../440".to_string()]).is_err());
+
+        for component in ["..", ".", "", "../etc", r"..\etc", "a/b", "C:"] {
+            assert!(sanitize_component(component).is_err(), "{:?}", component);
+        }
+        assert_eq!(sanitize_component("save.dat"), Ok("save.dat"));
+
+        for path in [
+            "../../etc/passwd",
+            "config/../..",
+            r"a\..\b",
+            "/etc/passwd",
+            r"C:\Windows",
+            "remote//file",
+        ] {
+            assert!(sanitize_relative_path(path).is_err(), "{:?}", path);
+        }
+        assert!(sanitize_relative_path("remote/save1.dat").is_ok());
+        assert!(sanitize_relative_path(r"remote\save1.dat").is_ok());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn non_utf8_names() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let dir = scratch_dir("non-utf8");
+        let src = dir.join("src");
+        let dst = dir.join("dst");
+        let name = OsStr::from_bytes(b"save\xff.dat");
+        fs::create_dir_all(src.join(OsStr::from_bytes(b"slot\xfe"))).unwrap();
+        fs::write(src.join(name), b"exotic").unwrap();
+        fs::write(
+            src.join(OsStr::from_bytes(b"slot\xfe")).join(name),
+            b"nested",
+        )
+        .unwrap();
+
+        let mut stats = CopyStats::default();
+        copy_dir_recursive(&src, &dst, &CopyOptions::default(), &mut stats).unwrap();
+        assert_eq!(fs::read(dst.join(name)).unwrap(), b"exotic");
+        assert_eq!(
+            fs::read(dst.join(OsStr::from_bytes(b"slot\xfe")).join(name)).unwrap(),
+            b"nested"
+        );
+
+        assert_eq!(
+            numeric_folder_name(&dir.join(OsStr::from_bytes(b"12\xff"))),
+            None
+        );
+        assert_eq!(
+            numeric_folder_name(&dir.join("12345678")),
+            Some("12345678".to_string())
+        );
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn swap_onto_itself_keeps_the_data() {
+        let dir = scratch_dir("self-swap");
+        let ud = dir.join("userdata");
+        let game = ud.join("111").join("440");
+        fs::create_dir_all(&game).unwrap();
+        fs::write(game.join("a.sav"), b"keep").unwrap();
+
+        let targets = ["111".to_string()];
+        assert!(reject_self_target("111", false, &targets, &SwapOptions::default()).is_err());
+        assert!(reject_self_target("111", true, &targets, &SwapOptions::default()).is_ok());
+
+        for source_base in [ud.join("111"), ud.join("111").join(".")] {
+            let mut journal = Vec::new();
+            let result = swap_into_targets(
+                &ud,
+                &source_base,
+                &targets,
+                &["440".to_string()],
+                &SwapOptions::default(),
+                &mut journal,
+            );
+            assert!(!result.success);
+            assert!(journal.is_empty());
+            assert_eq!(fs::read(game.join("a.sav")).unwrap(), b"keep");
+        }
+        assert!(!backups_dir(&ud).join("111").exists());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn offline_anonymous_and_regular_accounts() {
+        assert_eq!(to_steamid64("0"), None);
+        assert_eq!(to_steamid64("12345678"), Some(STEAMID64_BASE + 12345678));
+        assert_eq!(to_steamid64("anonymous"), None);
+
+        let dir = scratch_dir("special-accounts");
+        let ud = dir.join("userdata");
+        for id in ["0", "anonymous", "12345678"] {
+            let config = ud.join(id).join("config");
+            fs::create_dir_all(&config).unwrap();
+            fs::write(
+                config.join("localconfig.vdf"),
+                "\"UserLocalConfigStore\"\n{\n}\n",
+            )
+            .unwrap();
+        }
+        assert_eq!(numeric_folder_name(&ud.join("0")), Some("0".to_string()));
+        assert_eq!(numeric_folder_name(&ud.join("anonymous")), None);
+
+        let profiles = discover_profiles(&ud, &dir.join("Steam"), &[]);
+        let ids: Vec<&str> = profiles.iter().map(|p| p.id.as_str()).collect();
+        assert!(!ids.contains(&"anonymous"));
+
+        let offline = profiles.iter().find(|p| p.id == "0").unwrap();
+        assert!(offline.is_offline_account);
+        assert_eq!(offline.steamid64, None);
+
+        let regular = profiles.iter().find(|p| p.id == "12345678").unwrap();
+        assert!(!regular.is_offline_account);
+        assert_eq!(
+            regular.steamid64,
+            Some((STEAMID64_BASE + 12345678).to_string())
+        );
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn file_swaps_are_journaled_and_rolled_back() {
+        let dir = scratch_dir("file-swap-journal");
+        let ud = dir.join("userdata");
+        let source_game = ud.join("111").join("440");
+        let target_game = ud.join("222").join("440");
+        fs::create_dir_all(&source_game).unwrap();
+        fs::create_dir_all(&target_game).unwrap();
+        fs::write(source_game.join("a.sav"), b"source a").unwrap();
+        fs::write(source_game.join("b.sav"), b"source b").unwrap();
+        fs::write(target_game.join("a.sav"), b"target a").unwrap();
+        fs::write(target_game.join("c.sav"), b"target c").unwrap();
+        let swap = |files: &[&str], stop_on_error: bool| {
+            let options = SwapOptions {
+                files: Some(files.iter().map(|f| f.to_string()).collect()),
+                stop_on_error,
+                ..SwapOptions::default()
+            };
+            let mut journal = Vec::new();
+            let result = swap_into_targets(
+                &ud,
+                &ud.join("111"),
+                &["222".to_string()],
+                &["440".to_string()],
+                &options,
+                &mut journal,
+            );
+            (result, journal)
+        };
+        let assert_untouched = || {
+            assert_eq!(fs::read(target_game.join("a.sav")).unwrap(), b"target a");
+            assert!(!target_game.join("b.sav").exists());
+            assert_eq!(fs::read(target_game.join("c.sav")).unwrap(), b"target c");
+        };
+
+        // The missing file fails after a.sav and b.sav were written
+        let (result, journal) = swap(&["a.sav", "b.sav", "missing.sav"], true);
+        assert!(!result.success);
+        assert!(journal.is_empty());
+        assert_untouched();
+
+        let (result, journal) = swap(&["a.sav", "b.sav", "missing.sav"], false);
+        assert!(!result.success);
+        let files = journal[0].games[0].files.as_ref().unwrap();
+        assert_eq!(files.len(), 2);
+        assert!(files[0].had_data && !files[1].had_data);
+        assert_eq!(fs::read(target_game.join("b.sav")).unwrap(), b"source b");
+
+        let undone = undo_journal_entry(&ud, &new_journal_entry("111".to_string(), journal));
+        assert!(undone.success, "{:?}", undone.details);
+        assert_untouched();
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn profiles_sort_by_raw_last_login() {
+        let profile = |id: &str, last_login_secs: u64, is_backup: bool| Profile {
+            id: id.to_string(),
+            name: id.to_string(),
+            game_count: 1,
+            is_backup,
+            path: String::new(),
+            last_login: format_timestamp(last_login_secs),
+            last_login_secs,
+            steamid64: None,
+            avatar_path: None,
+            has_local_config: true,
+            read_error: None,
+            is_offline_account: false,
+            drive: String::new(),
+            total_size_bytes: 0,
+            total_size: String::new(),
+        };
+        // Compared as US-formatted strings, 12/31/2023 would sort ahead of 01/02/2024
+        let mut profiles = vec![
+            profile("backup", 1_800_000_000, true),
+            profile("never", 0, false),
+            profile("2023", 1_704_000_000, false),
+            profile("2024", 1_704_200_000, false),
+        ];
+        sort_profiles(&mut profiles, ProfileSort::LastLogin);
+        let ids: Vec<&str> = profiles.iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(ids, ["2024", "2023", "never", "backup"]);
+    }
+
+    #[test]
+    fn timestamps_keep_their_default_timezones() {
+        let preference = TimePreference::default();
+        assert!(matches!(preference.format, TimeFormat::Iso));
+        assert_eq!(preference.utc, None);
+        assert_eq!(format_timestamp(0), "Never");
+        // Steam timestamps stay in UTC and file times in local time, as they always were
+        assert_eq!(format_timestamp(1_709_301_909), "2024-03-01 14:05:09");
+        let time = SystemTime::UNIX_EPOCH + Duration::from_secs(1_709_301_909);
+        assert_eq!(
+            format_system_time(time),
+            chrono::DateTime::<chrono::Local>::from(time)
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn backups_are_kept_in_separate_snapshots() {
+        let _serial = SWAP_GUARD_TESTS.lock().unwrap();
+        let dir = scratch_dir("backup-snapshots");
+        let ud = dir.join("userdata");
+        let game = ud.join("111").join("440");
+        fs::create_dir_all(&game).unwrap();
+        fs::create_dir_all(ud.join("222").join("440")).unwrap();
+        fs::write(ud.join("222").join("440").join("a.sav"), b"source").unwrap();
+        let userdata_path = ud.to_string_lossy().to_string();
+        let backup = || {
+            backup_profile(
+                userdata_path.clone(),
+                "111".into(),
+                vec!["440".into()],
+                None,
+            )
+        };
+
+        fs::write(game.join("a.sav"), b"first").unwrap();
+        assert!(backup().success);
+        fs::write(game.join("a.sav"), b"second").unwrap();
+        assert!(backup().success);
+        let snapshots = list_account_snapshots(&backups_dir(&ud).join("111"));
+        assert_eq!(snapshots.len(), 2);
+        assert_eq!(
+            fs::read(snapshots[0].path.join("440").join("a.sav")).unwrap(),
+            b"second"
+        );
+        assert_eq!(
+            fs::read(snapshots[1].path.join("440").join("a.sav")).unwrap(),
+            b"first"
+        );
+        assert!(sanitize_backup_id(&snapshots[0].id()).is_ok());
+
+        // A later swap gets a snapshot of its own, so the first one can still be undone
+        let swap = || {
+            let mut journal = Vec::new();
+            swap_into_targets(
+                &ud,
+                &ud.join("222"),
+                &["111".to_string()],
+                &["440".to_string()],
+                &SwapOptions::default(),
+                &mut journal,
+            );
+            new_journal_entry("222".to_string(), journal)
+        };
+        let first = swap();
+        fs::write(game.join("a.sav"), b"between").unwrap();
+        swap();
+        let undone = undo_journal_entry(&ud, &first);
+        assert!(undone.success, "{:?}", undone.details);
+        assert_eq!(fs::read(game.join("a.sav")).unwrap(), b"second");
+
+        // The old single slot layout is moved into a snapshot when backups are listed
+        let legacy = backups_dir(&ud).join("333");
+        fs::create_dir_all(legacy.join("440")).unwrap();
+        fs::write(legacy.join("440").join("a.sav"), b"legacy").unwrap();
+        let snapshots = list_account_snapshots(&legacy);
+        assert_eq!(snapshots.len(), 1);
+        assert!(!legacy.join("440").exists());
+        assert_eq!(
+            fs::read(snapshots[0].path.join("440").join("a.sav")).unwrap(),
+            b"legacy"
+        );
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn labels_are_part_of_the_snapshot_name() {
+        let _serial = SWAP_GUARD_TESTS.lock().unwrap();
+        assert_eq!(
+            parse_snapshot_name("20240301-140509_before raid"),
+            Some((1_709_301_909, Some("before raid".to_string())))
+        );
+        assert_eq!(
+            parse_snapshot_name("20240301-140509"),
+            Some((1_709_301_909, None))
+        );
+        assert_eq!(parse_snapshot_name("20240301-140509_"), None);
+        assert_eq!(parse_snapshot_name("440"), None);
+
+        let dir = scratch_dir("backup-labels");
+        let ud = dir.join("userdata");
+        fs::create_dir_all(ud.join("111").join("440")).unwrap();
+        fs::write(ud.join("111").join("440").join("a.sav"), b"source").unwrap();
+        fs::create_dir_all(ud.join("222").join("440")).unwrap();
+        fs::write(ud.join("222").join("440").join("a.sav"), b"target").unwrap();
+        fs::create_dir_all(ud.join("333")).unwrap();
+
+        let options = SwapOptions {
+            backup_label: Some("before raid.".to_string()),
+            allow_new_targets: true,
+            ..SwapOptions::default()
+        };
+        let mut journal = Vec::new();
+        swap_into_targets(
+            &ud,
+            &ud.join("111"),
+            &["222".to_string(), "333".to_string()],
+            &["440".to_string()],
+            &options,
+            &mut journal,
+        );
+        // 333 had nothing to back up, so it gets no (labeled) snapshot at all
+        assert!(list_account_snapshots(&backups_dir(&ud).join("333")).is_empty());
+        let snapshots = list_account_snapshots(&backups_dir(&ud).join("222"));
+        assert_eq!(snapshots.len(), 1);
+        assert_eq!(snapshots[0].label.as_deref(), Some("before raid"));
+
+        // Relabeling renames the folder, the journal still finds it by its time
+        let userdata_path = ud.to_string_lossy().to_string();
+        let renamed =
+            set_backup_label(userdata_path.clone(), snapshots[0].id(), "kept".into()).unwrap();
+        assert!(renamed.ends_with("_kept"));
+        let cleared = set_backup_label(userdata_path, renamed, String::new()).unwrap();
+        assert_eq!(sanitize_backup_id(&cleared).unwrap().1.len(), 15);
+
+        let undone = undo_journal_entry(&ud, &new_journal_entry("111".to_string(), journal));
+        assert!(undone.success, "{:?}", undone.details);
+        assert_eq!(
+            fs::read(ud.join("222").join("440").join("a.sav")).unwrap(),
+            b"target"
+        );
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}